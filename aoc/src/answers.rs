@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Accepted answers of a day, stored in `answers.toml` next to its input.
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct Answers {
+    part_one: Option<toml::Value>,
+    part_two: Option<toml::Value>,
+    /// Ids of the day 2 games generated impossible on purpose, to check
+    /// which games the solver rules out and not only their sum
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    impossible: Option<Vec<i32>>,
+}
+
+impl Answers {
+    pub fn new(part_one: impl Into<toml::Value>, part_two: impl Into<toml::Value>) -> Answers {
+        Answers {
+            part_one: Some(part_one.into()),
+            part_two: Some(part_two.into()),
+            impossible: None,
+        }
+    }
+
+    pub fn with_impossible(self, ids: Vec<i32>) -> Answers {
+        Answers {
+            impossible: Some(ids),
+            ..self
+        }
+    }
+
+    /// Where the answers of `input` are stored.
+    pub fn path(input: &Path) -> PathBuf {
+        input.with_file_name("answers.toml")
+    }
+
+    /// Loads the answers stored next to `input`. A missing file just means
+    /// nothing has been accepted yet for that input.
+    pub fn load(input: &Path) -> Result<Answers, String> {
+        let path = Answers::path(input);
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let contents =
+            fs::read_to_string(&path).map_err(|error| format!("{}: {error}", path.display()))?;
+        toml::from_str(&contents).map_err(|error| format!("{}: {error}", path.display()))
+    }
+
+    pub fn get(&self, part: u8) -> Option<String> {
+        let value = if part == 1 {
+            &self.part_one
+        } else {
+            &self.part_two
+        };
+
+        value.as_ref().map(|value| match value {
+            toml::Value::String(answer) => answer.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    /// Stores the answers next to `input`, where `load` finds them.
+    pub fn save(&self, input: &Path) -> Result<(), String> {
+        let path = Answers::path(input);
+        let contents = toml::to_string(self).unwrap();
+        fs::write(&path, contents).map_err(|error| format!("{}: {error}", path.display()))
+    }
+}
@@ -0,0 +1,62 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Summary of the wall time of running the same phase many times.
+#[derive(Debug, Serialize)]
+pub struct Timings {
+    pub iterations: usize,
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "p95_ns", serialize_with = "as_nanos")]
+    pub p95: Duration,
+}
+
+fn as_nanos<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u128(duration.as_nanos())
+}
+
+impl Timings {
+    pub fn new(mut samples: Vec<Duration>) -> Timings {
+        samples.sort();
+        // Nearest rank, so with a handful of samples p95 is the slowest one
+        let rank = |percent: usize| (samples.len() * percent).div_ceil(100).max(1) - 1;
+
+        Timings {
+            iterations: samples.len(),
+            min: samples[0],
+            median: samples[rank(50)],
+            p95: samples[rank(95)],
+        }
+    }
+}
+
+/// Runs `f` `iterations` times, returning the last result and its timings.
+pub fn measure<T>(iterations: usize, mut f: impl FnMut() -> T) -> (T, Timings) {
+    let mut samples = Vec::with_capacity(iterations);
+    let mut out = None;
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        out = Some(f());
+        samples.push(start.elapsed());
+    }
+
+    (out.unwrap(), Timings::new(samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timings_use_nearest_rank() {
+        let samples = (1..=20).rev().map(Duration::from_millis).collect();
+        let timings = Timings::new(samples);
+
+        assert_eq!(timings.iterations, 20);
+        assert_eq!(timings.min, Duration::from_millis(1));
+        assert_eq!(timings.median, Duration::from_millis(10));
+        assert_eq!(timings.p95, Duration::from_millis(19));
+    }
+}
@@ -0,0 +1,412 @@
+mod answers;
+mod bench;
+mod registry;
+
+use answers::Answers;
+use clap::{Parser, Subcommand};
+use common::{Format, Part, Report, Runner, Verbosity, STDIN};
+use serde::Serialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[command(flatten)]
+    verbosity: Verbosity,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve one day, or every day with --all
+    Run(RunArgs),
+    /// Time the parsing and both parts of one day, or every day with --all
+    Bench(BenchArgs),
+    /// Write a random input for a day together with its answers.toml
+    Gen(GenArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct Selection {
+    /// Day of the calendar we want to solve
+    #[arg(short, long, required_unless_present = "all")]
+    day: Option<u8>,
+
+    /// Solve every registered day using its dayN/input.txt
+    #[arg(long, conflicts_with_all = ["day", "input"])]
+    all: bool,
+
+    /// Path to the file with the input, read from stdin when omitted or -
+    #[arg(short, long)]
+    input: Option<String>,
+}
+
+impl Selection {
+    /// Days to run together with the path of their input.
+    fn days(&self) -> Vec<(Box<dyn Runner>, String)> {
+        if self.all {
+            return registry::days()
+                .into_iter()
+                .map(|day| {
+                    let input = default_input(day.day());
+                    (day, input)
+                })
+                .collect();
+        }
+
+        let number = self.day.unwrap();
+        let Some(day) = registry::find(number) else {
+            common::exit_with(format!("day {number} is not solved yet"));
+        };
+        let input = self.input.clone().unwrap_or_else(|| STDIN.to_string());
+        vec![(day, input)]
+    }
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Part of the problem we want to solve, both by default
+    #[arg(short, long, value_enum, default_value_t = Part::Both)]
+    part: Part,
+
+    /// Check the answers against the answers.toml stored next to the input
+    #[arg(long)]
+    verify: bool,
+
+    /// Output format of the answers
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(clap::Args, Debug)]
+struct BenchArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Part of the problem we want to time, both by default
+    #[arg(short, long, value_enum, default_value_t = Part::Both)]
+    part: Part,
+
+    /// Number of times every phase is run
+    #[arg(short = 'n', long, default_value_t = 10)]
+    iterations: usize,
+
+    /// Output format of the results
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(clap::Args, Debug)]
+struct GenArgs {
+    /// Day to generate an input for, day 1 or day 2
+    #[arg(short, long)]
+    day: u8,
+
+    /// Seed of the generator, the same seed always gives the same input
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+
+    /// Where to write the input, the answers go to answers.toml next to it,
+    /// with the ids of the games made impossible for day 2. Without it the
+    /// input is printed and the answers go to stderr
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Overwrite the input and answers.toml if they already exist, which
+    /// could be the real puzzle ones
+    #[arg(long, requires = "output")]
+    force: bool,
+
+    /// Number of lines of the input, one game per line for day 2
+    #[arg(long, default_value_t = 1000)]
+    lines: usize,
+
+    #[command(flatten)]
+    day1: Day1Gen,
+
+    #[command(flatten)]
+    day2: Day2Gen,
+}
+
+/// Options only the day 1 generator takes. They have no default in clap so
+/// giving them for another day can be told apart and rejected.
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Day 1")]
+struct Day1Gen {
+    /// Chance of a digit being spelled out as a word, from 0 to 1 [default:
+    /// 0.5]
+    #[arg(long, value_parser = chance)]
+    word_density: Option<f64>,
+
+    /// Chance of a spelled digit being two words sharing letters, like
+    /// "eightwo", from 0 to 1 [default: 0.1]
+    #[arg(long, value_parser = chance)]
+    overlaps: Option<f64>,
+}
+
+/// Options only the day 2 generator takes.
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Day 2")]
+struct Day2Gen {
+    /// Bag the games draw their cubes from and are checked against
+    /// [default: red=12,green=13,blue=14]
+    #[arg(long)]
+    bag: Option<day2::Bag>,
+
+    /// Chance of a game drawing more cubes of a colour than the bag has,
+    /// from 0 to 1 [default: 0.3]
+    #[arg(long, value_parser = chance)]
+    impossible: Option<f64>,
+}
+
+impl GenArgs {
+    /// Options given for a day other than the one generated.
+    fn misplaced(&self) -> Vec<&'static str> {
+        let day1 = [
+            ("--word-density", self.day1.word_density.is_some()),
+            ("--overlaps", self.day1.overlaps.is_some()),
+        ];
+        let day2 = [
+            ("--bag", self.day2.bag.is_some()),
+            ("--impossible", self.day2.impossible.is_some()),
+        ];
+        let others = match self.day {
+            1 => day2.iter().collect::<Vec<_>>(),
+            2 => day1.iter().collect(),
+            // There is no generator to misplace them for
+            _ => vec![],
+        };
+        others
+            .into_iter()
+            .filter(|(_, given)| *given)
+            .map(|(flag, _)| *flag)
+            .collect()
+    }
+}
+
+fn chance(value: &str) -> Result<f64, String> {
+    let chance: f64 = value
+        .parse()
+        .map_err(|_| format!("{value:?} is not a number"))?;
+    if !(0.0..=1.0).contains(&chance) {
+        return Err(format!("{chance} is not between 0 and 1"));
+    }
+    Ok(chance)
+}
+
+fn default_input(day: u8) -> String {
+    format!("day{day}/input.txt")
+}
+
+fn read_input(input: &str) -> String {
+    common::read_input(input).unwrap_or_else(|error| common::exit_with(format!("{input}: {error}")))
+}
+
+/// Solves one day and prints its answers. Returns `false` when the day could
+/// not be solved or, when verifying, an answer does not match the recorded one.
+fn run_day(day: &dyn Runner, input: &str, args: &RunArgs) -> bool {
+    if args.verify && input == STDIN {
+        common::exit_with("--verify needs --input to find the recorded answers");
+    }
+
+    let mut reports = Report::solve(day, args.part, input);
+    if args.verify && reports.iter().any(|report| report.answer.is_some()) {
+        let answers = Answers::load(Path::new(input)).unwrap_or_else(|error| {
+            common::exit_with(format!("could not read the recorded answers: {error}"))
+        });
+        for report in reports.iter_mut() {
+            report.expected = answers.get(report.part);
+            report.verified = report
+                .expected
+                .as_ref()
+                .map(|expected| report.answer.as_ref() == Some(expected));
+        }
+    }
+    let succeeded = reports
+        .iter()
+        .all(|report| report.error.is_none() && report.verified != Some(false));
+
+    for report in reports {
+        if args.format == Format::Json {
+            println!("{}", report.to_json());
+            continue;
+        }
+
+        if let Some(error) = &report.error {
+            // Every part shares the error when the input could not be parsed
+            eprintln!("error: day {} ({input}): {error}", day.day());
+            break;
+        }
+
+        let answer = report.answer.as_deref().unwrap_or_default();
+        let label = format!("Day {} part {}", day.day(), report.part);
+        match (&report.expected, args.verify) {
+            (Some(expected), true) if expected != answer => {
+                eprintln!("{label}: {answer} does not match the recorded answer {expected}")
+            }
+            (Some(_), true) => println!("{label}: {answer} (ok)"),
+            (None, true) => println!("{label}: {answer} (no recorded answer)"),
+            _ if args.selection.all => println!("{label}: {answer} ({})", report.timings()),
+            _ if args.part == Part::Both => {
+                println!("Part {}: {answer} ({})", report.part, report.timings())
+            }
+            _ => println!("{answer}"),
+        }
+    }
+
+    succeeded
+}
+
+fn run(args: RunArgs) {
+    let mut succeeded = true;
+    for (day, input) in args.selection.days() {
+        succeeded &= run_day(day.as_ref(), &input, &args);
+    }
+
+    if !succeeded {
+        if args.verify {
+            eprintln!("Verification failed");
+        }
+        process::exit(1);
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct PhaseTimings {
+    day: u8,
+    phase: &'static str,
+    #[serde(flatten)]
+    timings: bench::Timings,
+}
+
+fn day_failed(day: &dyn Runner, error: Box<dyn Error>) -> ! {
+    common::exit_with(format!("day {}: {error}", day.day()))
+}
+
+fn bench_day(day: &dyn Runner, input: &str, args: &BenchArgs) -> Vec<PhaseTimings> {
+    let contents = read_input(input);
+    let (parsed, timings) = bench::measure(args.iterations, || day.parse(&contents));
+    let parsed = parsed.unwrap_or_else(|error| day_failed(day, error));
+    let mut out = vec![PhaseTimings {
+        day: day.day(),
+        phase: "parse",
+        timings,
+    }];
+
+    for part in args.part.numbers() {
+        let (answer, timings) = bench::measure(args.iterations, || parsed.solve(*part));
+        answer.unwrap_or_else(|error| day_failed(day, error));
+        out.push(PhaseTimings {
+            day: day.day(),
+            phase: if *part == 1 { "part_one" } else { "part_two" },
+            timings,
+        });
+    }
+
+    out
+}
+
+fn bench(args: BenchArgs) {
+    let mut results = vec![];
+    for (day, input) in args.selection.days() {
+        results.extend(bench_day(day.as_ref(), &input, &args));
+    }
+
+    if args.format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&results).unwrap());
+        return;
+    }
+
+    println!(
+        "{:>3}  {:<8}  {:>12}  {:>12}  {:>12}",
+        "day", "phase", "min", "median", "p95"
+    );
+    for result in results {
+        println!(
+            "{:>3}  {:<8}  {:>12?}  {:>12?}  {:>12?}",
+            result.day, result.phase, result.timings.min, result.timings.median, result.timings.p95
+        );
+    }
+}
+
+fn generate(args: GenArgs) {
+    if let [flag, ..] = args.misplaced()[..] {
+        common::exit_with(format!("{flag} does not apply to day {}", args.day));
+    }
+    if let (Some(output), false) = (&args.output, args.force) {
+        for path in [output.clone(), Answers::path(output)] {
+            if path.exists() {
+                common::exit_with(format!(
+                    "{} already exists, pass --force to overwrite it",
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    let (input, answers) = match args.day {
+        1 => {
+            let generator = day1::Generator {
+                lines: args.lines,
+                word_density: args.day1.word_density.unwrap_or(0.5),
+                overlaps: args.day1.overlaps.unwrap_or(0.1),
+            };
+            let generated = generator.generate(args.seed);
+            let answers = Answers::new(generated.part_one as i64, generated.part_two as i64);
+            (generated.input, answers)
+        }
+        2 => {
+            let bag = args.day2.bag.unwrap_or_default();
+            if bag.total() < 1 {
+                common::exit_with(format!("the bag {bag} has no cubes to draw"));
+            }
+            if bag.total() > day2::Generator::MAX_CUBES {
+                common::exit_with(format!(
+                    "the bag {bag} has more than {} cubes to draw",
+                    day2::Generator::MAX_CUBES
+                ));
+            }
+            let generator = day2::Generator {
+                games: args.lines,
+                bag,
+                impossible: args.day2.impossible.unwrap_or(0.3),
+            };
+            let generated = generator
+                .generate(args.seed)
+                .unwrap_or_else(|error| common::exit_with(error));
+            let answers = Answers::new(generated.part_one, generated.part_two)
+                .with_impossible(generated.impossible);
+            (generated.input, answers)
+        }
+        day => common::exit_with(format!("there is no generator for day {day}")),
+    };
+
+    let Some(output) = args.output else {
+        print!("{input}");
+        eprint!("{}", toml::to_string(&answers).unwrap());
+        return;
+    };
+    fs::write(&output, &input)
+        .unwrap_or_else(|error| common::exit_with(format!("{}: {error}", output.display())));
+    answers
+        .save(&output)
+        .unwrap_or_else(|error| common::exit_with(error));
+}
+
+fn main() {
+    let cli = Cli::parse();
+    cli.verbosity.init();
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::Bench(args) => bench(args),
+        Command::Gen(args) => generate(args),
+    }
+}
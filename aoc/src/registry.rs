@@ -0,0 +1,23 @@
+use common::Runner;
+use day1::Day1;
+use day2::Day2;
+use day3::Day3;
+use day4::Day4;
+use day5::Day5;
+use day6::Day6;
+
+/// Every solved day of the calendar in order, as the puzzle asks for them.
+pub fn days() -> Vec<Box<dyn Runner>> {
+    vec![
+        Box::new(Day1::default()),
+        Box::new(Day2::default()),
+        Box::new(Day3),
+        Box::new(Day4),
+        Box::new(Day5),
+        Box::new(Day6),
+    ]
+}
+
+pub fn find(number: u8) -> Option<Box<dyn Runner>> {
+    days().into_iter().find(|day| day.day() == number)
+}
@@ -0,0 +1,349 @@
+mod logger;
+
+pub use logger::Verbosity;
+
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::process;
+use std::time::{Duration, Instant};
+
+/// Input path meaning the input is read from stdin.
+pub const STDIN: &str = "-";
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    /// Part of the problem we want to solve, both by default
+    #[arg(short, long, value_enum, default_value_t = Part::Both)]
+    pub part: Part,
+
+    /// Path to the file with the input, read from stdin when omitted or -
+    #[arg(short, long, default_value = STDIN)]
+    pub input: String,
+
+    /// Output format of the answer
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    #[command(flatten)]
+    pub verbosity: Verbosity,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn numbers(&self) -> &'static [u8] {
+        match self {
+            Part::One => &[1],
+            Part::Two => &[2],
+            Part::Both => &[1, 2],
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// Outcome of solving one part of a day, what `--format json` prints.
+#[derive(Serialize, Debug)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+    /// Time spent parsing the input, shared by every part solved in the run
+    pub parse_ms: f64,
+    /// Time spent solving this part once the input was parsed
+    pub elapsed_ms: f64,
+    pub input_path: String,
+}
+
+impl Report {
+    /// Reads the input at `input_path` and solves the asked parts of `day`,
+    /// parsing it only once. Any error ends up in the reports instead of the
+    /// answers.
+    pub fn solve(day: &dyn Runner, part: Part, input_path: &str) -> Vec<Report> {
+        let mut reports: Vec<Report> = part
+            .numbers()
+            .iter()
+            .map(|part| Report {
+                day: day.day(),
+                part: *part,
+                answer: None,
+                error: None,
+                expected: None,
+                verified: None,
+                parse_ms: 0.0,
+                elapsed_ms: 0.0,
+                input_path: input_path.to_string(),
+            })
+            .collect();
+        let fail = |reports: &mut Vec<Report>, error: String| {
+            for report in reports.iter_mut() {
+                report.error = Some(error.clone());
+            }
+        };
+
+        let contents = match read_input(input_path) {
+            Ok(contents) => contents,
+            Err(error) => {
+                fail(&mut reports, format!("{input_path}: {error}"));
+                return reports;
+            }
+        };
+
+        let start = Instant::now();
+        let parsed = day.parse(&contents);
+        let parse_ms = as_millis(start.elapsed());
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                fail(&mut reports, error.to_string());
+                return reports;
+            }
+        };
+
+        for report in reports.iter_mut() {
+            let start = Instant::now();
+            let result = parsed.solve(report.part);
+            report.parse_ms = parse_ms;
+            report.elapsed_ms = as_millis(start.elapsed());
+            match result {
+                Ok(answer) => report.answer = Some(answer),
+                Err(error) => report.error = Some(error.to_string()),
+            }
+        }
+
+        reports
+    }
+
+    /// Human readable timings of the report.
+    pub fn timings(&self) -> String {
+        format!(
+            "parse {:.3}ms, solve {:.3}ms",
+            self.parse_ms, self.elapsed_ms
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+/// Where in the input something went wrong, lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+impl Location {
+    /// Location of `text`, which has to be a slice of `line`, the
+    /// `line_index` line (starting at 0) of the input.
+    pub fn new(line_index: usize, line: &str, text: &str) -> Location {
+        let offset = (text.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+        let column = match line.get(..offset) {
+            Some(before) => before.chars().count() + 1,
+            None => 1,
+        };
+
+        Location {
+            line: line_index + 1,
+            column,
+            text: text.to_string(),
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {:?}",
+            self.line, self.column, self.text
+        )
+    }
+}
+
+/// A day of the calendar: the input is parsed once and then both parts are
+/// solved from the parsed version.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Answer: Display;
+    type Error: Error + 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Self::Error>;
+    fn part_one(&self, input: &Self::Input) -> Result<Self::Answer, Self::Error>;
+    fn part_two(&self, input: &Self::Input) -> Result<Self::Answer, Self::Error>;
+}
+
+/// Parsed input of some day, with the answers already turned into strings so
+/// different days can be handled the same way.
+pub trait Parsed {
+    fn part_one(&self) -> Result<String, Box<dyn Error>>;
+    fn part_two(&self) -> Result<String, Box<dyn Error>>;
+
+    fn solve(&self, part: u8) -> Result<String, Box<dyn Error>> {
+        if part == 1 {
+            self.part_one()
+        } else {
+            self.part_two()
+        }
+    }
+}
+
+/// Object safe version of `Solution`, so the runner can keep every day in the
+/// same list.
+pub trait Runner {
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed + '_>, Box<dyn Error>>;
+}
+
+struct ParsedInput<'a, S: Solution> {
+    solution: &'a S,
+    input: S::Input,
+}
+
+impl<S: Solution> Parsed for ParsedInput<'_, S> {
+    fn part_one(&self) -> Result<String, Box<dyn Error>> {
+        Ok(self.solution.part_one(&self.input)?.to_string())
+    }
+
+    fn part_two(&self) -> Result<String, Box<dyn Error>> {
+        Ok(self.solution.part_two(&self.input)?.to_string())
+    }
+}
+
+impl<S> Runner for S
+where
+    S: Solution + 'static,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed + '_>, Box<dyn Error>> {
+        Ok(Box::new(ParsedInput {
+            solution: self,
+            input: Solution::parse(self, input)?,
+        }))
+    }
+}
+
+/// Solves the day as asked in the command line and prints the answer, exiting
+/// with a non-zero code when it could not be solved.
+pub fn run(day: &dyn Runner, args: &Args) {
+    args.verbosity.init();
+    let reports = Report::solve(day, args.part, &args.input);
+    for report in &reports {
+        match (&report.answer, args.format) {
+            (_, Format::Json) => println!("{}", report.to_json()),
+            (Some(answer), _) if args.part == Part::Both => {
+                println!("Part {}: {answer} ({})", report.part, report.timings())
+            }
+            (Some(answer), _) => println!("{answer}"),
+            (None, _) => {}
+        }
+    }
+
+    if let Some(error) = reports.into_iter().find_map(|report| report.error) {
+        exit_with(error);
+    }
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Reads the whole input from `path`, or from stdin when it is `-`.
+pub fn read_input(path: &str) -> io::Result<String> {
+    if path == STDIN {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents)?;
+        return Ok(contents);
+    }
+
+    fs::read_to_string(path)
+}
+
+/// Opens `path`, or stdin when it is `-`, to be read line by line instead
+/// of all at once, for inputs too big to hold in memory.
+pub fn open_input(path: &str) -> io::Result<Box<dyn BufRead>> {
+    if path == STDIN {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+
+    Ok(Box::new(BufReader::new(File::open(path)?)))
+}
+
+/// Line read from an input without its line ending, either "\n" or "\r\n".
+pub fn trim_newline(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Prints the answers of the parts in `args`, solved some other way than
+/// `run`, the way `run` prints them as text.
+pub fn print_answers(args: &Args, answers: [Option<impl Display>; 2]) {
+    for (part, answer) in (1..).zip(answers) {
+        match answer {
+            Some(answer) if args.part == Part::Both => println!("Part {part}: {answer}"),
+            Some(answer) => println!("{answer}"),
+            None => {}
+        }
+    }
+}
+
+/// Prints the error for the user and exits with a non-zero code, meant for
+/// the binaries so a bad input never ends in a panic.
+pub fn exit_with(error: impl Display) -> ! {
+    eprintln!("error: {error}");
+    process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn location_counts_columns_in_chars() {
+        let line = "Game 1: 3 blüe, x red";
+        let text = &line[line.find('x').unwrap()..];
+        let location = Location::new(4, line, &text[..1]);
+
+        assert_eq!(location.line, 5);
+        assert_eq!(location.column, 17);
+        assert_eq!(location.text, "x");
+    }
+
+    #[test]
+    fn trims_unix_and_windows_line_endings() {
+        assert_eq!(trim_newline(b"abc\n"), b"abc");
+        assert_eq!(trim_newline(b"abc\r\n"), b"abc");
+        assert_eq!(trim_newline(b"abc"), b"abc");
+        assert_eq!(trim_newline(b"a\rbc\n\n"), b"a\rbc\n");
+    }
+}
@@ -0,0 +1,61 @@
+use clap::ArgAction;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+// Verbosity flags shared by every binary. Logs always go to stderr so the
+// answer is the only thing printed on stdout.
+#[derive(clap::Args, Debug, Clone, Copy)]
+pub struct Verbosity {
+    /// Log more details to stderr, -v for debug and -vv for trace
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Only log errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+}
+
+impl Verbosity {
+    pub fn level(&self) -> LevelFilter {
+        if self.quiet {
+            return LevelFilter::Error;
+        }
+
+        match self.verbose {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+
+    /// Installs the stderr logger, can only be called once per process.
+    pub fn init(&self) {
+        static LOGGER: StderrLogger = StderrLogger;
+        log::set_logger(&LOGGER).expect("logger should only be set once");
+        log::set_max_level(self.level());
+    }
+}
+
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let level = match record.level() {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        eprintln!("[{level} {}] {}", record.target(), record.args());
+    }
+
+    fn flush(&self) {}
+}
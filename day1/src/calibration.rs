@@ -0,0 +1,174 @@
+use crate::{digits, Digit, Vocabulary};
+
+/// Which digits at the ends of a line make its calibration value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extract {
+    /// The first and the last N digits, joined together
+    Digits(usize),
+    /// The first and the last whole numbers, runs of digits right next to
+    /// each other, joined together
+    Numbers,
+}
+
+impl Default for Extract {
+    fn default() -> Extract {
+        Extract::Digits(1)
+    }
+}
+
+/// Digits taken from one end of a line, read as a single number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct End {
+    /// None when the number does not fit in 64 bits
+    pub value: Option<u64>,
+    /// Byte offset where the first of the digits starts
+    pub position: usize,
+    /// Length in bytes from the first digit to the end of the last one
+    pub len: usize,
+    pub digits: u32,
+    /// How many of the digits were spelled out as words
+    pub words: u32,
+}
+
+impl End {
+    fn new(digit: Digit) -> End {
+        End {
+            value: Some(digit.value as u64),
+            position: digit.position,
+            len: digit.len,
+            digits: 1,
+            words: digit.spelled as u32,
+        }
+    }
+
+    /// Byte offset right after the last of the digits.
+    pub fn end(&self) -> usize {
+        self.position + self.len
+    }
+
+    /// Adds a digit found after the ones already taken.
+    fn push_back(&mut self, digit: Digit, radix: u64) {
+        self.value = self
+            .value
+            .and_then(|value| value.checked_mul(radix))
+            .and_then(|value| value.checked_add(digit.value as u64));
+        self.len = digit.position + digit.len - self.position;
+        self.digits += 1;
+        self.words += digit.spelled as u32;
+    }
+
+    /// Adds a digit found before the ones already taken.
+    fn push_front(&mut self, digit: Digit, radix: u64) {
+        self.value = radix
+            .checked_pow(self.digits)
+            .and_then(|weight| weight.checked_mul(digit.value as u64))
+            .zip(self.value)
+            .and_then(|(front, value)| front.checked_add(value));
+        self.len = self.end() - digit.position;
+        self.position = digit.position;
+        self.digits += 1;
+        self.words += digit.spelled as u32;
+    }
+}
+
+/// Digits at both ends of a line. When the line is short both ends can be
+/// made of the same digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    pub first: End,
+    pub last: End,
+    radix: u64,
+}
+
+impl Calibration {
+    /// Looks for the first digits from the front and the last ones from the
+    /// back, so the middle of the line is never scanned.
+    pub fn find<L>(line: &L, vocabulary: &Vocabulary, extract: Extract) -> Option<Calibration>
+    where
+        L: AsRef<[u8]> + ?Sized,
+    {
+        let radix = vocabulary.radix() as u64;
+        let mut forward = digits(line, vocabulary);
+        let mut first = End::new(forward.next()?);
+        let mut backward = digits(line, vocabulary).rev();
+        let mut last = End::new(backward.next()?);
+
+        match extract {
+            Extract::Digits(count) => {
+                for digit in forward.take(count.saturating_sub(1)) {
+                    first.push_back(digit, radix);
+                }
+                for digit in backward.take(count.saturating_sub(1)) {
+                    last.push_front(digit, radix);
+                }
+            }
+            Extract::Numbers => {
+                for digit in forward {
+                    if digit.position != first.end() {
+                        break;
+                    }
+                    first.push_back(digit, radix);
+                }
+                for digit in backward {
+                    if digit.position + digit.len != last.position {
+                        break;
+                    }
+                    last.push_front(digit, radix);
+                }
+            }
+        }
+
+        Some(Calibration { first, last, radix })
+    }
+
+    /// Both ends joined as a single number, None when it does not fit in 64
+    /// bits.
+    pub fn value(&self) -> Option<u64> {
+        self.radix
+            .checked_pow(self.last.digits)
+            .zip(self.first.value)
+            .and_then(|(weight, first)| first.checked_mul(weight))
+            .zip(self.last.value)
+            .and_then(|(first, last)| first.checked_add(last))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(line: &str, vocabulary: &Vocabulary, extract: Extract) -> Option<u64> {
+        Calibration::find(line, vocabulary, extract).and_then(|calibration| calibration.value())
+    }
+
+    #[test]
+    fn joins_several_digits_from_each_end() {
+        let english = Vocabulary::default();
+        assert_eq!(value("a1b2c3d4e", &english, Extract::Digits(2)), Some(1234));
+        assert_eq!(value("eightwo5", &english, Extract::Digits(2)), Some(8225));
+        assert_eq!(value("7", &english, Extract::Digits(3)), Some(77));
+        assert_eq!(value("treb7uchet", &english, Extract::default()), Some(77));
+    }
+
+    #[test]
+    fn takes_whole_numbers_at_both_ends() {
+        let numerals = Vocabulary::numerals();
+        assert_eq!(
+            value("GET /items/42 took 318ms", &numerals, Extract::Numbers),
+            Some(42318)
+        );
+        assert_eq!(value("x12y", &numerals, Extract::Numbers), Some(1212));
+        assert_eq!(
+            value(
+                "ff 0x1A",
+                &numerals.clone().with_radix(16),
+                Extract::Numbers
+            ),
+            Some(0xff1a)
+        );
+        assert_eq!(
+            value("1 99999999999999999999", &numerals, Extract::Numbers),
+            None
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::Vocabulary;
+
+/// A digit found in a line, either as a numeral or spelled out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Digit {
+    pub value: u32,
+    /// Byte offset of the digit in the line
+    pub position: usize,
+    /// Length in bytes of the numeral or the word
+    pub len: usize,
+    /// Whether the digit was spelled out as a word instead of a numeral
+    pub spelled: bool,
+}
+
+/// Scans a line for digits without allocating, from the front or from the
+/// back. Spelled out digits are looked for at every offset, so words sharing
+/// letters like "eightwo" give both digits, but a word lying wholly inside a
+/// longer one like "III" in "VIII" is not a digit of its own.
+pub struct Digits<'a> {
+    line: &'a [u8],
+    /// Next offset checked from the front
+    position: usize,
+    /// Offset after the next one checked from the back
+    end: usize,
+    vocabulary: &'a Vocabulary,
+}
+
+/// Every digit of `line` in order, either numerals or words of `vocabulary`.
+pub fn digits<'a, L>(line: &'a L, vocabulary: &'a Vocabulary) -> Digits<'a>
+where
+    L: AsRef<[u8]> + ?Sized,
+{
+    let line = line.as_ref();
+    Digits {
+        line,
+        position: 0,
+        end: line.len(),
+        vocabulary,
+    }
+}
+
+impl Digits<'_> {
+    /// Whether a word starting before `position` spans over all the `len`
+    /// bytes from it. The letters of "fifteen" are not hexadecimal digits of
+    /// their own, nor is "IV" in "XIV".
+    fn inside_word(&self, position: usize, len: usize) -> bool {
+        let start = position.saturating_sub(self.vocabulary.longest_word());
+        (start..position).any(|start| {
+            self.vocabulary
+                .find_at(&self.line[start..])
+                .is_some_and(|(word, _)| start + word.len() >= position + len)
+        })
+    }
+
+    fn digit_at(&self, position: usize) -> Option<Digit> {
+        let rest = &self.line[position..];
+        // Words go first, in hexadecimal "eight" is a word and not an "e".
+        // They are valid UTF-8 too, so they can never match starting in the
+        // middle of a multi-byte character
+        if let Some((word, value)) = self.vocabulary.find_at(rest) {
+            if self.inside_word(position, word.len()) {
+                return None;
+            }
+            return Some(Digit {
+                value,
+                position,
+                len: word.len(),
+                spelled: true,
+            });
+        }
+
+        let value = (rest[0] as char).to_digit(self.vocabulary.radix())?;
+        if self.inside_word(position, 1) {
+            return None;
+        }
+        Some(Digit {
+            value,
+            position,
+            len: 1,
+            spelled: false,
+        })
+    }
+}
+
+impl Iterator for Digits<'_> {
+    type Item = Digit;
+
+    fn next(&mut self) -> Option<Digit> {
+        while self.position < self.end {
+            let digit = self.digit_at(self.position);
+            self.position += 1;
+            if digit.is_some() {
+                return digit;
+            }
+        }
+
+        None
+    }
+}
+
+impl DoubleEndedIterator for Digits<'_> {
+    fn next_back(&mut self) -> Option<Digit> {
+        while self.end > self.position {
+            self.end -= 1;
+            let digit = self.digit_at(self.end);
+            if digit.is_some() {
+                return digit;
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(line: &str) -> Vec<(u32, usize)> {
+        digits(line, &Vocabulary::default())
+            .map(|digit| (digit.value, digit.position))
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_words_give_both_digits() {
+        assert_eq!(found("eightwo"), vec![(8, 0), (2, 4)]);
+        assert_eq!(found("oneight"), vec![(1, 0), (8, 2)]);
+        assert_eq!(found("twone"), vec![(2, 0), (1, 2)]);
+        assert_eq!(found("sevenine"), vec![(7, 0), (9, 4)]);
+        assert_eq!(
+            found("threeightwone"),
+            vec![(3, 0), (8, 4), (2, 8), (1, 10)]
+        );
+    }
+
+    #[test]
+    fn numerals_and_words_are_mixed_in_order() {
+        assert_eq!(found("xtwone3four"), vec![(2, 1), (1, 3), (3, 6), (4, 7)]);
+        assert_eq!(
+            found("4nineeightseven2"),
+            vec![(4, 0), (9, 1), (8, 5), (7, 10), (2, 15)]
+        );
+        assert_eq!(found("7pqrstsixteen"), vec![(7, 0), (6, 6)]);
+        assert_eq!(found("six"), vec![(6, 0)]);
+    }
+
+    #[test]
+    fn partial_words_and_other_text_are_ignored() {
+        assert_eq!(found(""), vec![]);
+        assert_eq!(found("zero"), vec![]);
+        assert_eq!(found("fiv thre nin"), vec![]);
+        assert_eq!(found("ñone"), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn scanning_from_the_back_finds_the_same_digits() {
+        for line in [
+            "threeightwone",
+            "xtwone3four",
+            "7pqrstsixteen",
+            "ñone",
+            "abc",
+        ] {
+            let mut backwards: Vec<Digit> = digits(line, &Vocabulary::default()).rev().collect();
+            backwards.reverse();
+            assert_eq!(
+                backwards,
+                digits(line, &Vocabulary::default()).collect::<Vec<Digit>>()
+            );
+        }
+
+        let english = Vocabulary::default();
+        let mut both_ends = digits("twone", &english);
+        assert_eq!(both_ends.next_back().map(|digit| digit.value), Some(1));
+        assert_eq!(both_ends.next().map(|digit| digit.value), Some(2));
+        assert_eq!(both_ends.next(), None);
+    }
+
+    #[test]
+    fn numerals_only_skips_words() {
+        let numerals = Vocabulary::numerals();
+        let values: Vec<u32> = digits("one2three4", &numerals).map(|d| d.value).collect();
+        assert_eq!(values, vec![2, 4]);
+    }
+
+    #[test]
+    fn numerals_and_words_follow_the_base() {
+        let hex = Vocabulary::default().with_radix(16);
+        let values: Vec<u32> = digits("fifteen3Beight", &hex).map(|d| d.value).collect();
+        assert_eq!(values, vec![15, 3, 11, 8]);
+
+        let binary = Vocabulary::numerals().with_radix(2);
+        let values: Vec<u32> = digits("1021", &binary).map(|d| d.value).collect();
+        assert_eq!(values, vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn other_vocabularies_use_the_same_rules() {
+        let german = Vocabulary::preset("german").unwrap();
+        let values: Vec<u32> = digits("xfünfzweins", &german).map(|d| d.value).collect();
+        assert_eq!(values, vec![5, 2, 1]);
+    }
+
+    #[test]
+    fn words_inside_longer_words_are_not_digits() {
+        let roman = Vocabulary::preset("roman").unwrap();
+        let values: Vec<u32> = digits("VIII", &roman).map(|d| d.value).collect();
+        assert_eq!(values, vec![8]);
+        let values: Vec<u32> = digits("IIIxVII", &roman).rev().map(|d| d.value).collect();
+        assert_eq!(values, vec![7, 3]);
+
+        // "XIV" is not a digit in base ten, so "IV" is
+        let values: Vec<u32> = digits("XIV", &roman).map(|d| d.value).collect();
+        assert_eq!(values, vec![4]);
+        let roman = roman.with_radix(16);
+        let values: Vec<u32> = digits("XIV", &roman).map(|d| d.value).collect();
+        assert_eq!(values, vec![14]);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{Calibration, End};
+use std::env;
+use std::io::{self, IsTerminal};
+
+const FIRST_COLOR: &str = "\x1b[1;32m";
+const LAST_COLOR: &str = "\x1b[1;36m";
+const RESET: &str = "\x1b[0m";
+
+/// How the first and last digits stand out in the explained line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// Bold green for the first digit and bold cyan for the last one
+    Ansi,
+    /// `[` and `]` around both digits, for pipes and files
+    Markers,
+}
+
+impl Highlight {
+    /// Colors when stdout is a terminal, unless `NO_COLOR` is set.
+    pub fn detect() -> Highlight {
+        if io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none() {
+            Highlight::Ansi
+        } else {
+            Highlight::Markers
+        }
+    }
+
+    fn wrap(&self, text: &str, color: &str) -> String {
+        match self {
+            Highlight::Ansi => format!("{color}{text}{RESET}"),
+            Highlight::Markers => format!("[{text}]"),
+        }
+    }
+}
+
+/// Column of the digits as people count it, from 1 and in characters.
+fn column(line: &str, end: &End) -> usize {
+    line[..end.position].chars().count() + 1
+}
+
+fn describe(line: &str, end: &End) -> String {
+    let text = &line[end.position..end.end()];
+    let kind = match (end.words, end.digits) {
+        (0, 1) => "numeral",
+        (0, _) => "numerals",
+        (1, 1) => "word",
+        (words, digits) if words == digits => "words",
+        _ => "numerals and words",
+    };
+    format!("{text:?} ({kind}) at column {}", column(line, end))
+}
+
+/// One line of the input with its first and last digits highlighted, where
+/// they were found and the value they add to the total.
+pub fn explain(
+    line_number: usize,
+    line: &str,
+    calibration: Option<&Calibration>,
+    highlight: Highlight,
+) -> String {
+    let Some(calibration) = calibration else {
+        return format!("{line_number:>4}: {line}  no digits");
+    };
+    let Calibration { first, last, .. } = calibration;
+
+    let mut highlighted = line[..first.position].to_string();
+    highlighted.push_str(&highlight.wrap(&line[first.position..first.end()], FIRST_COLOR));
+    if first != last {
+        // Overlapping words like "twone" can make the last digits start
+        // inside the first ones, the first ones keep the shared letters
+        let last_start = last.position.max(first.end());
+        let last_end = last.end().max(last_start);
+        highlighted.push_str(&line[first.end()..last_start]);
+        if last_start < last_end {
+            highlighted.push_str(&highlight.wrap(&line[last_start..last_end], LAST_COLOR));
+        }
+    }
+    highlighted.push_str(&line[first.end().max(last.end())..]);
+
+    let value = match calibration.value() {
+        Some(value) => value.to_string(),
+        None => "too large".to_string(),
+    };
+    if first == last {
+        return format!(
+            "{line_number:>4}: {highlighted}  only {} -> {value}",
+            describe(line, first)
+        );
+    }
+    format!(
+        "{line_number:>4}: {highlighted}  first {}, last {} -> {value}",
+        describe(line, first),
+        describe(line, last)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Extract, Vocabulary};
+
+    fn explained(line: &str) -> String {
+        let calibration = Calibration::find(line, &Vocabulary::default(), Extract::default());
+        explain(1, line, calibration.as_ref(), Highlight::Markers)
+    }
+
+    #[test]
+    fn marks_first_and_last_digit() {
+        assert_eq!(
+            explained("xtwone3four"),
+            "   1: x[two]ne3[four]  first \"two\" (word) at column 2, \
+             last \"four\" (word) at column 8 -> 24"
+        );
+        assert_eq!(
+            explained("treb7uchet"),
+            "   1: treb[7]uchet  only \"7\" (numeral) at column 5 -> 77"
+        );
+        assert_eq!(explained("abc"), "   1: abc  no digits");
+    }
+
+    #[test]
+    fn overlapping_words_share_their_letters() {
+        assert_eq!(
+            explained("twone"),
+            "   1: [two][ne]  first \"two\" (word) at column 1, \
+             last \"one\" (word) at column 3 -> 21"
+        );
+        assert_eq!(
+            explain(
+                7,
+                "ñ1",
+                Calibration::find("ñ1", &Vocabulary::numerals(), Extract::default()).as_ref(),
+                Highlight::Ansi
+            ),
+            "   7: ñ\x1b[1;32m1\x1b[0m  only \"1\" (numeral) at column 2 -> 11"
+        );
+    }
+
+    #[test]
+    fn whole_numbers_are_marked_at_once() {
+        let line = "id 42 took 318ms";
+        let calibration = Calibration::find(line, &Vocabulary::numerals(), Extract::Numbers);
+        assert_eq!(
+            explain(1, line, calibration.as_ref(), Highlight::Markers),
+            "   1: id [42] took [318]ms  first \"42\" (numerals) at column 4, \
+             last \"318\" (numerals) at column 12 -> 42318"
+        );
+    }
+}
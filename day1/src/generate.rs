@@ -0,0 +1,159 @@
+use fastrand::Rng;
+
+/// Letters that are in no English digit word, so filler can never complete
+/// a word by accident.
+const FILLER: &[u8] = b"abcdjklmpqyz";
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Pairs of words sharing letters and the two digits they give.
+const OVERLAPS: [(&str, u32, u32); 8] = [
+    ("oneight", 1, 8),
+    ("twone", 2, 1),
+    ("threeight", 3, 8),
+    ("fiveight", 5, 8),
+    ("sevenine", 7, 9),
+    ("eightwo", 8, 2),
+    ("eighthree", 8, 3),
+    ("nineight", 9, 8),
+];
+
+/// Most digits written in a single line.
+const MAX_DIGITS: usize = 8;
+/// Most filler letters between two digits.
+const MAX_FILLER: usize = 4;
+
+/// Settings for random calibration documents in English.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Generator {
+    pub lines: usize,
+    /// Chance of a digit being spelled out as a word instead of a numeral
+    pub word_density: f64,
+    /// Chance of a spelled digit being a pair of words sharing letters
+    pub overlaps: f64,
+}
+
+/// A generated document with the totals it must add up to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub part_one: u64,
+    pub part_two: u64,
+}
+
+/// One piece of a line with the first and last digit it gives when words
+/// count, and its value when only numerals do.
+struct Token {
+    text: String,
+    first: u32,
+    last: u32,
+    numeral: bool,
+}
+
+impl Generator {
+    /// Writes the document and works out its totals as it goes, without
+    /// going through the solver.
+    pub fn generate(&self, seed: u64) -> Generated {
+        let mut rng = Rng::with_seed(seed);
+        let mut generated = Generated {
+            input: String::new(),
+            part_one: 0,
+            part_two: 0,
+        };
+
+        for _ in 0..self.lines {
+            let mut tokens: Vec<Token> = (0..rng.usize(1..=MAX_DIGITS))
+                .map(|_| self.token(&mut rng))
+                .collect();
+            // Part one needs a numeral in every line
+            if !tokens.iter().any(|token| token.numeral) {
+                let value = rng.u32(1..=9);
+                let numeral = Token {
+                    text: value.to_string(),
+                    first: value,
+                    last: value,
+                    numeral: true,
+                };
+                tokens.insert(rng.usize(0..=tokens.len()), numeral);
+            }
+
+            let numerals: Vec<&Token> = tokens.iter().filter(|token| token.numeral).collect();
+            let (first, last) = (numerals[0], numerals[numerals.len() - 1]);
+            generated.part_one += (first.first * 10 + last.last) as u64;
+            let (first, last) = (&tokens[0], &tokens[tokens.len() - 1]);
+            generated.part_two += (first.first * 10 + last.last) as u64;
+
+            filler(&mut rng, &mut generated.input);
+            for token in tokens {
+                generated.input.push_str(&token.text);
+                filler(&mut rng, &mut generated.input);
+            }
+            generated.input.push('\n');
+        }
+
+        generated
+    }
+
+    fn token(&self, rng: &mut Rng) -> Token {
+        if rng.f64() >= self.word_density {
+            let value = rng.u32(1..=9);
+            return Token {
+                text: value.to_string(),
+                first: value,
+                last: value,
+                numeral: true,
+            };
+        }
+
+        if rng.f64() < self.overlaps {
+            let (text, first, last) = OVERLAPS[rng.usize(..OVERLAPS.len())];
+            return Token {
+                text: text.to_string(),
+                first,
+                last,
+                numeral: false,
+            };
+        }
+
+        let value = rng.u32(1..=9);
+        Token {
+            text: WORDS[value as usize - 1].to_string(),
+            first: value,
+            last: value,
+            numeral: false,
+        }
+    }
+}
+
+fn filler(rng: &mut Rng, out: &mut String) {
+    for _ in 0..rng.usize(0..=MAX_FILLER) {
+        out.push(FILLER[rng.usize(..FILLER.len())] as char);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day1;
+    use common::Solution;
+
+    #[test]
+    fn totals_match_the_solver() {
+        let generator = Generator {
+            lines: 500,
+            word_density: 0.6,
+            overlaps: 0.5,
+        };
+        for seed in 0..10 {
+            let generated = generator.generate(seed);
+            let lines = Day1::default().parse(&generated.input).unwrap();
+            assert_eq!(Day1::default().part_one(&lines), Ok(generated.part_one));
+            assert_eq!(Day1::default().part_two(&lines), Ok(generated.part_two));
+        }
+
+        assert_eq!(generator.generate(3), generator.generate(3));
+        assert_ne!(generator.generate(3), generator.generate(4));
+    }
+}
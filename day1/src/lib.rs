@@ -0,0 +1,230 @@
+mod calibration;
+mod digits;
+mod explain;
+mod generate;
+mod stream;
+mod vocabulary;
+
+pub use calibration::{Calibration, End, Extract};
+pub use digits::{digits, Digit, Digits};
+pub use explain::{explain, Highlight};
+pub use generate::{Generated, Generator};
+pub use stream::{StreamError, Totals};
+pub use vocabulary::{Vocabulary, VocabularyError};
+
+use clap::ValueEnum;
+use common::{Location, Solution};
+use log::{trace, warn};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("no digits found at {0}")]
+    NoDigits(Location),
+    #[error("calibration value does not fit in 64 bits at {0}")]
+    TooLarge(Location),
+}
+
+/// Location of a whole line, which may not be valid UTF-8 when streaming.
+fn whole_line(line_index: usize, line: &[u8]) -> Location {
+    let line = String::from_utf8_lossy(line);
+    Location::new(line_index, &line, &line)
+}
+
+/// What to do with the lines that have no digits at all.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OnMissing {
+    /// Leave the line out of the total
+    Skip,
+    /// Count the line with a calibration value of 0
+    Zero,
+    /// Stop with an error pointing at the line
+    #[default]
+    Error,
+}
+
+/// Line numbers listed at most when summarizing the lines without digits.
+const LISTED_LINES: usize = 10;
+
+/// Lines without digits found so far. Only the first line numbers are kept
+/// for the summary, so huge inputs do not grow it.
+#[derive(Default)]
+struct MissingLines {
+    count: usize,
+    listed: Vec<usize>,
+}
+
+impl MissingLines {
+    fn push(&mut self, line_number: usize) {
+        self.count += 1;
+        if self.listed.len() < LISTED_LINES {
+            self.listed.push(line_number);
+        }
+    }
+
+    /// Logs the summary once per part, each part finding digits its own way.
+    fn warn(&self, on_missing: OnMissing, part: u8) {
+        if self.count == 0 {
+            return;
+        }
+
+        let listed: Vec<String> = self.listed.iter().map(|line| line.to_string()).collect();
+        let more = if self.count > LISTED_LINES {
+            ", ..."
+        } else {
+            ""
+        };
+        let action = match on_missing {
+            OnMissing::Zero => "counted as 0",
+            _ => "skipped",
+        };
+        warn!(
+            "part {part}: {} lines without digits {action}: {}{more}",
+            self.count,
+            listed.join(", ")
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct Day1 {
+    /// Words that count as digits in part two, and the base of every digit
+    pub vocabulary: Vocabulary,
+    pub on_missing: OnMissing,
+    pub extract: Extract,
+}
+
+impl Day1 {
+    /// Digits looked for in each part, numerals in the same base as the
+    /// words for part one.
+    pub fn vocabulary(&self, part: u8) -> Vocabulary {
+        match part {
+            1 => Vocabulary::numerals().with_radix(self.vocabulary.radix()),
+            _ => self.vocabulary.clone(),
+        }
+    }
+
+    /// Adds the calibration value of one line to `total`, lines without
+    /// digits add nothing unless they are an error.
+    fn add(
+        &self,
+        total: u64,
+        line_index: usize,
+        line: &[u8],
+        vocabulary: &Vocabulary,
+        missing: &mut MissingLines,
+    ) -> Result<u64, Error> {
+        let Some(calibration) = Calibration::find(line, vocabulary, self.extract) else {
+            if self.on_missing == OnMissing::Error {
+                return Err(Error::NoDigits(whole_line(line_index, line)));
+            }
+            missing.push(line_index + 1);
+            return Ok(total);
+        };
+
+        let value = calibration.value();
+        trace!("line {}: {value:?}", line_index + 1);
+        value
+            .and_then(|value| total.checked_add(value))
+            .ok_or_else(|| Error::TooLarge(whole_line(line_index, line)))
+    }
+
+    fn total(&self, lines: &[String], part: u8) -> Result<u64, Error> {
+        let vocabulary = self.vocabulary(part);
+        let mut total = 0;
+        let mut missing = MissingLines::default();
+        for (i, line) in lines.iter().enumerate() {
+            total = self.add(total, i, line.as_bytes(), &vocabulary, &mut missing)?;
+        }
+        missing.warn(self.on_missing, part);
+
+        Ok(total)
+    }
+}
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+    type Answer = u64;
+    type Error = Error;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, Error> {
+        Ok(input.lines().map(|line| line.to_string()).collect())
+    }
+
+    fn part_one(&self, lines: &Vec<String>) -> Result<u64, Error> {
+        self.total(lines, 1)
+    }
+
+    fn part_two(&self, lines: &Vec<String>) -> Result<u64, Error> {
+        self.total(lines, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+    const EXAMPLE_TWO: &str = include_str!("../example2.txt");
+
+    #[test]
+    fn part_one_example() {
+        assert_eq!(
+            Day1::default().part_one(&Day1::default().parse(EXAMPLE).unwrap()),
+            Ok(142)
+        );
+    }
+
+    #[test]
+    fn part_two_example() {
+        assert_eq!(
+            Day1::default().part_two(&Day1::default().parse(EXAMPLE_TWO).unwrap()),
+            Ok(281)
+        );
+    }
+
+    #[test]
+    fn roman_numerals_count_once() {
+        let day = Day1 {
+            vocabulary: Vocabulary::preset("roman").unwrap(),
+            ..Day1::default()
+        };
+        let lines = day
+            .parse(
+                "VIII
+III
+xIXy
+",
+            )
+            .unwrap();
+        assert_eq!(day.part_two(&lines), Ok(88 + 33 + 99));
+    }
+
+    #[test]
+    fn line_without_digits_is_an_error() {
+        let lines = Day1::default().parse("1abc2\nabc\n").unwrap();
+
+        let error = Day1::default().part_one(&lines).unwrap_err();
+        assert_eq!(
+            error,
+            Error::NoDigits(Location {
+                line: 2,
+                column: 1,
+                text: "abc".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn lines_without_digits_can_be_left_out() {
+        let day = Day1 {
+            on_missing: OnMissing::Skip,
+            ..Day1::default()
+        };
+        let lines = day.parse("1abc2\n\nabc\none7\n").unwrap();
+        assert_eq!(day.part_one(&lines), Ok(12 + 77));
+        assert_eq!(day.part_two(&lines), Ok(12 + 17));
+    }
+}
@@ -1,55 +1,103 @@
-use clap::{arg, command, Parser};
-use std::fs;
+use clap::Parser;
+use day1::{explain, Calibration, Day1, Extract, Highlight, OnMissing, Vocabulary};
+use log::debug;
+use std::time::Instant;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-    /// Part of the problem we want to solve 1 or 2, 1 by default
-    #[arg(short, long, default_value_t = 1)]
-    part: u8,
-
-    /// Path to the file with the input
-    #[arg(short, long)]
-    input: String,
-}
-fn to_numbers(input: String) -> String {
-    // The reasons I'm adding the character version of the numbers to the replacement is
-    // to support one character being used by two numbers eg. 4nineightseven2 -> 49872
-    // (and also because I'm quite bad with regex)
-    input
-        .replace("one", "one1one")
-        .replace("two", "two2two")
-        .replace("three", "three3three")
-        .replace("four", "four4four")
-        .replace("five", "five5five")
-        .replace("six", "siz6six")
-        .replace("seven", "seven7seven")
-        .replace("eight", "eight8eight")
-        .replace("nine", "nine9nine")
-        .replace("zero", "zero0zero")
+struct Cli {
+    #[command(flatten)]
+    common: common::Args,
+
+    /// Words spelling the digits in part two: english, spanish, german,
+    /// french, roman or the path to a file with a "<word> <digit>" per line
+    #[arg(long, default_value = "english")]
+    vocab: String,
+
+    /// Base of the digits, from 2 to 16. In hexadecimal a to f are numerals
+    /// and words like "ten" to "fifteen" count too
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(2..=16))]
+    base: u32,
+
+    /// Join the first N and the last N digits of every line
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    digits: u64,
+
+    /// Join the first and the last whole numbers of every line instead
+    #[arg(long, conflicts_with = "digits")]
+    numbers: bool,
+
+    /// What to do with lines without any digit, skipped and zero lines are
+    /// listed in a warning
+    #[arg(long, value_enum, default_value_t = OnMissing::Error)]
+    on_missing: OnMissing,
+
+    /// Print every line with the digits making its calibration value
+    #[arg(long, conflicts_with = "format")]
+    explain: bool,
+
+    /// Read the input line by line in constant memory, for huge inputs
+    #[arg(long, conflicts_with_all = ["format", "explain"])]
+    stream: bool,
 }
-fn main() {
-    let args = Args::parse();
-    println!("In file {}", args.input);
-
-    let contents = fs::read_to_string(args.input).expect("Should have been able to read the file");
-    let lines = contents.lines();
-    let mut total = 0;
-    for line in lines {
-        let mut line_string = line.to_string();
-        if args.part == 2 {
-            line_string = to_numbers(line_string);
-        }
 
-        let numbers: String = line_string.chars().filter(|c| c.is_numeric()).collect();
-        let two_digits_number_str = format!(
-            "{}{}",
-            numbers.chars().nth(0).unwrap().to_string(),
-            numbers.chars().nth(numbers.len() - 1).unwrap().to_string()
-        );
-        let number = two_digits_number_str.parse::<i32>().unwrap();
-        total = total + number;
+/// Goes through the input line by line showing where every calibration
+/// value comes from, instead of just printing the totals.
+fn explain_input(day: &Day1, args: &common::Args) {
+    args.verbosity.init();
+    let input = common::read_input(&args.input)
+        .unwrap_or_else(|error| common::exit_with(format!("{}: {error}", args.input)));
+    let highlight = Highlight::detect();
+
+    for part in args.part.numbers() {
+        let vocabulary = day.vocabulary(*part);
+        let mut total: u64 = 0;
+        println!("Part {part}:");
+        for (i, line) in input.lines().enumerate() {
+            let calibration = Calibration::find(line, &vocabulary, day.extract);
+            let value = calibration.and_then(|calibration| calibration.value());
+            total = total.saturating_add(value.unwrap_or(0));
+            println!("{}", explain(i + 1, line, calibration.as_ref(), highlight));
+        }
+        println!("Total: {total}");
     }
+}
+
+/// Solves the parts reading the input as it goes, without ever holding all
+/// of it in memory.
+fn stream_input(day: &Day1, args: &common::Args) {
+    args.verbosity.init();
+    let reader = common::open_input(&args.input)
+        .unwrap_or_else(|error| common::exit_with(format!("{}: {error}", args.input)));
+
+    let start = Instant::now();
+    let totals = day
+        .stream(reader, args.part.numbers())
+        .unwrap_or_else(|error| common::exit_with(error));
+    debug!("streamed the input in {:?}", start.elapsed());
 
-    print!("{total}");
+    common::print_answers(args, [totals.part_one, totals.part_two]);
+}
+
+fn main() {
+    let args = Cli::parse();
+    let vocabulary = Vocabulary::load(&args.vocab).unwrap_or_else(|error| common::exit_with(error));
+    let extract = match args.numbers {
+        true => Extract::Numbers,
+        false => Extract::Digits(args.digits as usize),
+    };
+    let day = Day1 {
+        vocabulary: vocabulary.with_radix(args.base),
+        on_missing: args.on_missing,
+        extract,
+    };
+    if args.explain {
+        explain_input(&day, &args.common);
+        return;
+    }
+    if args.stream {
+        stream_input(&day, &args.common);
+        return;
+    }
+    common::run(&day, &args.common);
 }
@@ -0,0 +1,104 @@
+use crate::{Day1, MissingLines, Vocabulary};
+use std::io::{self, BufRead};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StreamError {
+    #[error("could not read the input: {0}")]
+    Read(#[from] io::Error),
+    #[error(transparent)]
+    Calibration(#[from] crate::Error),
+}
+
+/// Totals of the parts that were asked for, wide enough for inputs of many
+/// gigabytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    pub part_one: Option<u64>,
+    pub part_two: Option<u64>,
+}
+
+impl Day1 {
+    /// Solves `parts` in a single pass over `reader`. Only the current line
+    /// is kept in memory and its buffer is reused, so the input can be
+    /// arbitrarily large.
+    pub fn stream(&self, mut reader: impl BufRead, parts: &[u8]) -> Result<Totals, StreamError> {
+        let start = |part: u8| {
+            parts
+                .contains(&part)
+                .then(|| (self.vocabulary(part), 0, MissingLines::default()))
+        };
+        let mut part_one = start(1);
+        let mut part_two = start(2);
+
+        let mut buffer = vec![];
+        let mut line_index = 0;
+        while reader.read_until(b'\n', &mut buffer)? > 0 {
+            let line = common::trim_newline(&buffer);
+            for (vocabulary, total, missing) in [&mut part_one, &mut part_two].into_iter().flatten()
+            {
+                *total = self.add(*total, line_index, line, vocabulary, missing)?;
+            }
+
+            buffer.clear();
+            line_index += 1;
+        }
+
+        let finish = |part: u8, solved: Option<(Vocabulary, u64, MissingLines)>| {
+            solved.map(|(_, total, missing)| {
+                missing.warn(self.on_missing, part);
+                total
+            })
+        };
+        Ok(Totals {
+            part_one: finish(1, part_one),
+            part_two: finish(2, part_two),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, Location, OnMissing};
+
+    #[test]
+    fn streams_both_parts_in_one_pass() {
+        let totals = Day1::default()
+            .stream("two1nine\n4nineeightseven2\n".as_bytes(), &[1, 2])
+            .unwrap();
+        assert_eq!(
+            totals,
+            Totals {
+                part_one: Some(11 + 42),
+                part_two: Some(29 + 42),
+            }
+        );
+
+        let day = Day1 {
+            on_missing: OnMissing::Skip,
+            ..Day1::default()
+        };
+        let totals = day.stream("two1\r\n\r\nthree".as_bytes(), &[2]).unwrap();
+        assert_eq!(totals.part_two, Some(21 + 33));
+        assert_eq!(totals.part_one, None);
+    }
+
+    #[test]
+    fn lines_without_digits_keep_their_number() {
+        let error = Day1::default()
+            .stream("1\nabc\r\n2".as_bytes(), &[1])
+            .unwrap_err();
+        let StreamError::Calibration(error) = error else {
+            panic!("expected a calibration error, found {error}");
+        };
+        assert_eq!(
+            error,
+            Error::NoDigits(Location {
+                line: 2,
+                column: 1,
+                text: "abc".to_string(),
+            })
+        );
+    }
+}
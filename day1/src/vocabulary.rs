@@ -0,0 +1,203 @@
+use std::cmp::Reverse;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+/// Built-in vocabularies, written as `word value` pairs like the vocabulary
+/// files. Values from 10 on only count for numbers in a base above ten.
+const PRESETS: [(&str, &str); 5] = [
+    (
+        "english",
+        "one 1\ntwo 2\nthree 3\nfour 4\nfive 5\nsix 6\nseven 7\neight 8\nnine 9\n\
+         ten 10\neleven 11\ntwelve 12\nthirteen 13\nfourteen 14\nfifteen 15",
+    ),
+    (
+        "spanish",
+        "uno 1\ndos 2\ntres 3\ncuatro 4\ncinco 5\nseis 6\nsiete 7\nocho 8\nnueve 9\n\
+         diez 10\nonce 11\ndoce 12\ntrece 13\ncatorce 14\nquince 15",
+    ),
+    (
+        "german",
+        "eins 1\nzwei 2\ndrei 3\nvier 4\nfünf 5\nsechs 6\nsieben 7\nacht 8\nneun 9\n\
+         zehn 10\nelf 11\nzwölf 12\ndreizehn 13\nvierzehn 14\nfünfzehn 15",
+    ),
+    (
+        "french",
+        "un 1\ndeux 2\ntrois 3\nquatre 4\ncinq 5\nsix 6\nsept 7\nhuit 8\nneuf 9\n\
+         dix 10\nonze 11\ndouze 12\ntreize 13\nquatorze 14\nquinze 15",
+    ),
+    (
+        "roman",
+        "I 1\nII 2\nIII 3\nIV 4\nV 5\nVI 6\nVII 7\nVIII 8\nIX 9\n\
+         X 10\nXI 11\nXII 12\nXIII 13\nXIV 14\nXV 15",
+    ),
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum VocabularyError {
+    #[error("unknown vocabulary {0:?}, expected one of english, spanish, german, french, roman or a file")]
+    Unknown(String),
+    #[error("could not read the vocabulary: {0}")]
+    Read(String),
+    #[error("line {line}: expected \"<word> <digit>\" but found {text:?}")]
+    InvalidEntry { line: usize, text: String },
+}
+
+/// Largest base numbers can be read in, digits go from 0 to f.
+pub const MAX_RADIX: u32 = 16;
+
+/// Words that spell out digits, matched case sensitively, and the base the
+/// digits are in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vocabulary {
+    /// Sorted from the longest word, so when several words start at the same
+    /// place the longest one wins ("VIII" over "V")
+    words: Vec<(String, u32)>,
+    /// Numerals and words with a value from the radix on are not digits
+    radix: u32,
+}
+
+impl Default for Vocabulary {
+    fn default() -> Vocabulary {
+        Vocabulary::preset("english").unwrap()
+    }
+}
+
+impl Vocabulary {
+    /// No words at all, only numerals count as digits.
+    pub fn numerals() -> Vocabulary {
+        Vocabulary {
+            words: vec![],
+            radix: 10,
+        }
+    }
+
+    /// The same words with digits in base `radix`, between 2 and 16.
+    pub fn with_radix(self, radix: u32) -> Vocabulary {
+        assert!((2..=MAX_RADIX).contains(&radix), "unsupported base {radix}");
+        Vocabulary { radix, ..self }
+    }
+
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    pub fn preset(name: &str) -> Option<Vocabulary> {
+        let (_, contents) = PRESETS.iter().find(|(preset, _)| *preset == name)?;
+        Some(Vocabulary::parse(contents).unwrap())
+    }
+
+    /// A preset name or the path of a vocabulary file. A file named like a
+    /// preset has to be written as a path, like `./english`.
+    pub fn load(name_or_path: &str) -> Result<Vocabulary, VocabularyError> {
+        if let Some(vocabulary) = Vocabulary::preset(name_or_path) {
+            return Ok(vocabulary);
+        }
+        // Not a path nor an existing file, most likely a misspelled preset
+        if !name_or_path.contains(['/', '.']) && !Path::new(name_or_path).exists() {
+            return Err(VocabularyError::Unknown(name_or_path.to_string()));
+        }
+
+        let contents = fs::read_to_string(name_or_path)
+            .map_err(|error| VocabularyError::Read(format!("{name_or_path}: {error}")))?;
+        Vocabulary::parse(&contents)
+    }
+
+    /// Parses one `word digit` pair per line, empty lines and lines starting
+    /// with `#` are skipped. Digits go up to 15 for numbers in hexadecimal,
+    /// the vocabulary starts in base ten.
+    pub fn parse(contents: &str) -> Result<Vocabulary, VocabularyError> {
+        let mut words = vec![];
+        for (i, line) in contents.lines().enumerate() {
+            let entry = line.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || VocabularyError::InvalidEntry {
+                line: i + 1,
+                text: line.to_string(),
+            };
+            let mut parts = entry.split_whitespace();
+            let (Some(word), Some(value), None) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(invalid());
+            };
+            let value = value
+                .parse::<u32>()
+                .ok()
+                .filter(|value| *value < MAX_RADIX)
+                .ok_or_else(invalid)?;
+            words.push((word.to_string(), value));
+        }
+        words.sort_by_key(|(word, _)| Reverse(word.len()));
+
+        Ok(Vocabulary { words, radix: 10 })
+    }
+
+    pub(crate) fn longest_word(&self) -> usize {
+        self.words.first().map_or(0, |(word, _)| word.len())
+    }
+
+    /// Word spelling a digit at the very start of `text` and its value, if any.
+    pub fn find_at(&self, text: &[u8]) -> Option<(&str, u32)> {
+        self.words
+            .iter()
+            .find(|(word, value)| *value < self.radix && text.starts_with(word.as_bytes()))
+            .map(|(word, value)| (word.as_str(), *value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_spell_every_digit() {
+        for (name, _) in PRESETS {
+            let vocabulary = Vocabulary::preset(name).unwrap();
+            let mut values: Vec<u32> = vocabulary.words.iter().map(|(_, value)| *value).collect();
+            values.sort();
+            assert_eq!(values, (1..MAX_RADIX).collect::<Vec<u32>>(), "{name}");
+        }
+    }
+
+    #[test]
+    fn longest_word_wins() {
+        let roman = Vocabulary::preset("roman").unwrap();
+        assert_eq!(roman.find_at(b"VIII"), Some(("VIII", 8)));
+        assert_eq!(roman.find_at(b"IVX"), Some(("IV", 4)));
+        assert_eq!(roman.find_at(b"X"), None);
+        assert_eq!(roman.find_at(b"XIV"), None);
+
+        let roman = roman.with_radix(16);
+        assert_eq!(roman.find_at(b"XIV"), Some(("XIV", 14)));
+    }
+
+    #[test]
+    fn files_map_words_to_digits() {
+        let vocabulary = Vocabulary::parse("# mine\nzero 0\n\nuno 1\n").unwrap();
+        assert_eq!(vocabulary.find_at(b"zero"), Some(("zero", 0)));
+        assert_eq!(vocabulary.find_at(b"uno"), Some(("uno", 1)));
+
+        assert_eq!(
+            Vocabulary::parse("uno 1\ndieciséis 16\n"),
+            Err(VocabularyError::InvalidEntry {
+                line: 2,
+                text: "dieciséis 16".to_string(),
+            })
+        );
+        assert_eq!(
+            Vocabulary::load("klingon"),
+            Err(VocabularyError::Unknown("klingon".to_string()))
+        );
+    }
+
+    #[test]
+    fn files_load_without_a_path() {
+        let name = "vocabulary-without-a-path";
+        fs::write(name, "uno 1\n").unwrap();
+        let vocabulary = Vocabulary::load(name);
+        fs::remove_file(name).unwrap();
+        assert_eq!(vocabulary.unwrap().find_at(b"uno"), Some(("uno", 1)));
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum BagError {
+    #[error("expected \"<colour>=<count>\" but found {0:?}")]
+    InvalidEntry(String),
+    #[error("{0} is listed more than once in the bag")]
+    RepeatedColour(String),
+    #[error("could not read the bags: {0}")]
+    Read(String),
+    #[error("no bags found in {0}")]
+    Empty(String),
+}
+
+/// Cubes of each colour in the bag the games are played with, in the order
+/// they were listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    cubes: Vec<(String, i32)>,
+}
+
+impl Default for Bag {
+    /// The bag of the puzzle.
+    fn default() -> Bag {
+        "red=12,green=13,blue=14".parse().unwrap()
+    }
+}
+
+impl FromStr for Bag {
+    type Err = BagError;
+
+    /// Parses `red=12,green=13,blue=14`, with any colours in any order, each
+    /// once, and no negative counts.
+    fn from_str(spec: &str) -> Result<Bag, BagError> {
+        let mut cubes = vec![];
+        for entry in spec.split(',') {
+            let invalid = || BagError::InvalidEntry(entry.trim().to_string());
+            let (colour, count) = entry.split_once('=').ok_or_else(invalid)?;
+            let colour = colour.trim();
+            let count = count
+                .trim()
+                .parse::<i32>()
+                .ok()
+                .filter(|count| *count >= 0)
+                .ok_or_else(invalid)?;
+            if colour.is_empty() {
+                return Err(invalid());
+            }
+            if cubes.iter().any(|(listed, _)| listed == colour) {
+                return Err(BagError::RepeatedColour(colour.to_string()));
+            }
+            cubes.push((colour.to_string(), count));
+        }
+
+        Ok(Bag { cubes })
+    }
+}
+
+impl FromIterator<(String, i32)> for Bag {
+    fn from_iter<I: IntoIterator<Item = (String, i32)>>(cubes: I) -> Bag {
+        Bag {
+            cubes: cubes.into_iter().collect(),
+        }
+    }
+}
+
+impl Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cubes: Vec<String> = self
+            .cubes
+            .iter()
+            .map(|(colour, count)| format!("{colour}={count}"))
+            .collect();
+        write!(f, "{}", cubes.join(","))
+    }
+}
+
+impl Bag {
+    /// Cubes of `colour` in the bag, None when the bag does not list it.
+    pub fn get(&self, colour: &str) -> Option<i32> {
+        self.cubes
+            .iter()
+            .find(|(listed, _)| listed == colour)
+            .map(|(_, count)| *count)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.cubes.iter().map(|(colour, _)| colour.as_str())
+    }
+
+    /// Cubes of every colour together, in i64 as the counts of a few
+    /// colours add up past i32.
+    pub fn total(&self) -> i64 {
+        self.cubes.iter().map(|(_, count)| *count as i64).sum()
+    }
+
+    /// A single bag written as `red=12,green=13,blue=14`, or the path of a
+    /// file with one bag like that per line. Empty lines and lines starting
+    /// with `#` are skipped.
+    pub fn load(spec_or_path: &str) -> Result<Vec<Bag>, BagError> {
+        if spec_or_path.contains('=') {
+            return Ok(vec![spec_or_path.parse()?]);
+        }
+
+        let contents = fs::read_to_string(spec_or_path)
+            .map_err(|error| BagError::Read(format!("{spec_or_path}: {error}")))?;
+        let bags = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::parse)
+            .collect::<Result<Vec<Bag>, BagError>>()?;
+        if bags.is_empty() {
+            return Err(BagError::Empty(spec_or_path.to_string()));
+        }
+
+        Ok(bags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_any_colours_in_any_order() {
+        let bag: Bag = "blue=14, red=12,yellow=3".parse().unwrap();
+        assert_eq!(bag.get("red"), Some(12));
+        assert_eq!(bag.get("yellow"), Some(3));
+        assert_eq!(bag.get("green"), None);
+        assert_eq!(bag.to_string(), "blue=14,red=12,yellow=3");
+        assert_eq!(Bag::default().to_string(), "red=12,green=13,blue=14");
+
+        assert_eq!(
+            "red=1,green".parse::<Bag>(),
+            Err(BagError::InvalidEntry("green".to_string()))
+        );
+        assert_eq!(
+            "red=1,=2".parse::<Bag>(),
+            Err(BagError::InvalidEntry("=2".to_string()))
+        );
+        assert_eq!(
+            "red=1, blue=-10".parse::<Bag>(),
+            Err(BagError::InvalidEntry("blue=-10".to_string()))
+        );
+        assert_eq!(
+            "red=12,green=13, red=5".parse::<Bag>(),
+            Err(BagError::RepeatedColour("red".to_string()))
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{Bag, Day2, Game};
+use std::fmt::{self, Display};
+
+/// A draw taking more cubes of a colour than the bag has.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Index of the draw in the game, from 1
+    pub draw: usize,
+    pub colour: String,
+    pub count: i32,
+    /// Cubes of the colour in the bag, None when the bag does not list it
+    pub limit: Option<i32>,
+}
+
+impl Violation {
+    pub fn excess(&self) -> i32 {
+        self.count - self.limit.unwrap_or(0)
+    }
+}
+
+/// Games that a bigger limit of a single colour would make possible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Raise {
+    pub colour: String,
+    /// Limits from the smallest, with the games each one adds to the
+    /// smaller ones
+    pub limits: Vec<(i32, Vec<i32>)>,
+}
+
+/// Why the games were impossible with a bag, and what a bigger bag would
+/// change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnosis {
+    pub bag: Bag,
+    /// Impossible games by id with every draw going over a limit
+    pub impossible: Vec<(i32, Vec<Violation>)>,
+    /// Games that are only over the limit of one colour, by colour
+    pub raises: Vec<Raise>,
+}
+
+impl Game {
+    /// Every draw of the game taking more cubes of a colour than `bag` has.
+    pub fn violations(&self, bag: &Bag) -> Vec<Violation> {
+        let mut out = vec![];
+        for (i, withdraw) in self.withdraws.iter().enumerate() {
+            for (colour, count) in withdraw.iter() {
+                let limit = bag.get(colour);
+                if count > limit.unwrap_or(0) {
+                    out.push(Violation {
+                        draw: i + 1,
+                        colour: colour.to_string(),
+                        count,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        out
+    }
+}
+
+impl Day2 {
+    pub fn diagnose(&self, games: &[Game], bag: &Bag) -> Diagnosis {
+        let mut impossible = vec![];
+        let mut raises: Vec<Raise> = vec![];
+        for game in games {
+            let violations = game.violations(bag);
+            let Some(first) = violations.first() else {
+                continue;
+            };
+
+            // Raising one colour only helps games where no other colour is
+            // over the limit
+            if violations
+                .iter()
+                .all(|violation| violation.colour == first.colour)
+            {
+                let needed = violations
+                    .iter()
+                    .map(|violation| violation.count)
+                    .max()
+                    .unwrap();
+                let index = match raises.iter().position(|raise| raise.colour == first.colour) {
+                    Some(index) => index,
+                    None => {
+                        raises.push(Raise {
+                            colour: first.colour.clone(),
+                            limits: vec![],
+                        });
+                        raises.len() - 1
+                    }
+                };
+                let limits = &mut raises[index].limits;
+                match limits.iter_mut().find(|(limit, _)| *limit == needed) {
+                    Some((_, ids)) => ids.push(game.id),
+                    None => limits.push((needed, vec![game.id])),
+                }
+            }
+            impossible.push((game.id, violations));
+        }
+        for raise in raises.iter_mut() {
+            raise.limits.sort();
+        }
+
+        Diagnosis {
+            bag: bag.clone(),
+            impossible,
+            raises,
+        }
+    }
+}
+
+fn join(ids: &[i32]) -> String {
+    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    ids.join(", ")
+}
+
+impl Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.impossible.is_empty() {
+            return writeln!(f, "Every game is possible with {}", self.bag);
+        }
+
+        writeln!(f, "Impossible games with {}:", self.bag)?;
+        for (id, violations) in &self.impossible {
+            writeln!(f, "Game {id}:")?;
+            for violation in violations {
+                let limit = match violation.limit {
+                    Some(limit) => format!("the limit of {limit}"),
+                    None => "a bag without any".to_string(),
+                };
+                writeln!(
+                    f,
+                    "  draw {}: {} {}, {} over {limit}",
+                    violation.draw,
+                    violation.count,
+                    violation.colour,
+                    violation.excess()
+                )?;
+            }
+        }
+
+        if self.raises.is_empty() {
+            return writeln!(f, "No game becomes possible raising a single colour");
+        }
+        writeln!(f, "Raising a single colour:")?;
+        for Raise { colour, limits } in &self.raises {
+            let mut ids = vec![];
+            for (limit, added) in limits {
+                ids.extend(added);
+                ids.sort();
+                let games = if ids.len() == 1 { "game" } else { "games" };
+                writeln!(
+                    f,
+                    "  {colour}={limit} makes {games} {} possible",
+                    join(&ids)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn points_at_the_draws_over_the_limits() {
+        let day = Day2::default();
+        let games = day.parse(EXAMPLE).unwrap();
+        let diagnosis = day.diagnose(&games, &day.bag);
+
+        let ids: Vec<i32> = diagnosis.impossible.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![3, 4]);
+        assert_eq!(
+            diagnosis.impossible[0].1,
+            vec![Violation {
+                draw: 1,
+                colour: "red".to_string(),
+                count: 20,
+                limit: Some(12),
+            }]
+        );
+        // Game 4 goes over on red and blue, one colour is not enough
+        assert_eq!(
+            diagnosis.raises,
+            vec![Raise {
+                colour: "red".to_string(),
+                limits: vec![(20, vec![3])],
+            }]
+        );
+        assert_eq!(
+            diagnosis.to_string(),
+            "Impossible games with red=12,green=13,blue=14:\n\
+             Game 3:\n  draw 1: 20 red, 8 over the limit of 12\n\
+             Game 4:\n  draw 3: 15 blue, 1 over the limit of 14\n  draw 3: 14 red, 2 over the limit of 12\n\
+             Raising a single colour:\n  red=20 makes game 3 possible\n"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{Cubes, Day2, Error, Game};
+use clap::ValueEnum;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Export {
+    /// One row per draw, the columns of the game repeated in each of them
+    Csv,
+    /// An array with one object per game
+    Json,
+}
+
+impl Serialize for Cubes {
+    /// A map from colour to count, in the order the colours were written.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.cubes.len()))?;
+        for (colour, count) in self.iter() {
+            map.serialize_entry(colour, &count)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Record<'a> {
+    id: i32,
+    withdraws: &'a [Cubes],
+    min_cubes: Cubes,
+    power: i64,
+}
+
+/// Quotes a CSV field when it would break the row otherwise.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        return format!("\"{}\"", text.replace('"', "\"\""));
+    }
+    text.to_string()
+}
+
+impl Day2 {
+    /// Every game with its draws, the fewest cubes it needs and its power,
+    /// for spreadsheets and notebooks.
+    pub fn export(&self, games: &[Game], format: Export) -> Result<String, Error> {
+        match format {
+            Export::Json => {
+                let mut records = vec![];
+                for game in games {
+                    records.push(Record {
+                        id: game.id,
+                        withdraws: &game.withdraws,
+                        min_cubes: game.min_cubes(),
+                        power: self.power(game)?,
+                    });
+                }
+                Ok(serde_json::to_string_pretty(&records).unwrap() + "\n")
+            }
+            Export::Csv => self.csv(games),
+        }
+    }
+
+    fn csv(&self, games: &[Game]) -> Result<String, Error> {
+        let colours = self.colours(games);
+        let mut header = vec!["game".to_string(), "draw".to_string()];
+        header.extend(colours.iter().map(|colour| csv_field(colour)));
+        header.extend(
+            colours
+                .iter()
+                .map(|colour| csv_field(&format!("min_{colour}"))),
+        );
+        header.push("power".to_string());
+
+        let mut out = header.join(",") + "\n";
+        for game in games {
+            let min_cubes = game.min_cubes();
+            let power = self.power(game)?;
+            for (i, withdraw) in game.withdraws.iter().enumerate() {
+                let mut row = vec![game.id as i64, i as i64 + 1];
+                row.extend(colours.iter().map(|colour| withdraw.get(colour) as i64));
+                row.extend(colours.iter().map(|colour| min_cubes.get(colour) as i64));
+                row.push(power);
+
+                let row: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+                out.push_str(&row.join(","));
+                out.push('\n');
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    #[test]
+    fn exports_every_draw_with_the_game_totals() {
+        let day = Day2::default();
+        let games = day
+            .parse("Game 1: 3 blue, 4 red; 2 green\nGame 7: 1 yellow")
+            .unwrap();
+
+        assert_eq!(
+            day.export(&games, Export::Csv).unwrap(),
+            "game,draw,red,green,blue,yellow,min_red,min_green,min_blue,min_yellow,power\n\
+             1,1,4,0,3,0,4,2,3,0,24\n\
+             1,2,0,2,0,0,4,2,3,0,24\n\
+             7,1,0,0,0,1,0,0,0,1,0\n"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&day.export(&games, Export::Json).unwrap()).unwrap();
+        assert_eq!(
+            json[0],
+            serde_json::json!({
+                "id": 1,
+                "withdraws": [{"blue": 3, "red": 4}, {"green": 2}],
+                "min_cubes": {"blue": 3, "red": 4, "green": 2},
+                "power": 24,
+            })
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{checked_power, Bag, Error};
+use fastrand::Rng;
+
+/// Most draws in a single game.
+const MAX_DRAWS: usize = 6;
+/// Most cubes an impossible draw takes over the limit of its colour.
+const MAX_EXCESS: i32 = 5;
+
+/// Settings for random game logs played with a real bag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    pub games: usize,
+    /// Bag the cubes are drawn from, and that the games are checked against
+    pub bag: Bag,
+    /// Chance of a game having a draw with more cubes of a colour than the
+    /// bag has
+    pub impossible: f64,
+}
+
+/// A generated log with the answers it must give with the generator's bag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub part_one: i64,
+    pub part_two: i64,
+    /// Ids of the games made impossible on purpose
+    pub impossible: Vec<i32>,
+}
+
+impl Generator {
+    /// Most cubes the bag can have, as every cube is held and shuffled
+    /// while drawing.
+    pub const MAX_CUBES: i64 = 100_000;
+
+    /// Plays the games and works out the answers as it goes, without going
+    /// through the solver. Each draw takes cubes out of the bag without
+    /// replacement and puts them all back before the next one.
+    ///
+    /// Fails when the powers add up past i64, which takes a bag with a lot
+    /// of colours. Panics when the bag has no cubes to draw or more than
+    /// `MAX_CUBES`.
+    pub fn generate(&self, seed: u64) -> Result<Generated, Error> {
+        assert!(
+            self.bag.total() <= Generator::MAX_CUBES,
+            "the bag {} has more than {} cubes",
+            self.bag,
+            Generator::MAX_CUBES
+        );
+        let mut rng = Rng::with_seed(seed);
+        let colours: Vec<&str> = self.bag.colours().collect();
+        let limits: Vec<i32> = colours
+            .iter()
+            .map(|colour| self.bag.get(colour).unwrap().max(0))
+            .collect();
+        // One entry per cube, with the index of its colour
+        let mut cubes: Vec<usize> = limits
+            .iter()
+            .enumerate()
+            .flat_map(|(colour, &limit)| std::iter::repeat_n(colour, limit as usize))
+            .collect();
+        assert!(!cubes.is_empty(), "the bag {} has no cubes", self.bag);
+
+        let mut generated = Generated {
+            input: String::new(),
+            part_one: 0,
+            part_two: 0,
+            impossible: vec![],
+        };
+        for id in 1..=self.games as i32 {
+            let mut draws: Vec<Vec<(usize, i32)>> = (0..rng.usize(1..=MAX_DRAWS))
+                .map(|_| draw(&mut rng, &mut cubes, colours.len()))
+                .collect();
+            if rng.f64() < self.impossible {
+                let colour = rng.usize(..colours.len());
+                let count = limits[colour] + rng.i32(1..=MAX_EXCESS);
+                let index = rng.usize(..draws.len());
+                let draw = &mut draws[index];
+                match draw.iter_mut().find(|(drawn, _)| *drawn == colour) {
+                    Some((_, drawn)) => *drawn = count,
+                    None => draw.insert(rng.usize(0..=draw.len()), (colour, count)),
+                }
+                generated.impossible.push(id);
+            } else {
+                generated.part_one += id as i64;
+            }
+
+            let mut fewest = vec![0; colours.len()];
+            for &(colour, count) in draws.iter().flatten() {
+                fewest[colour] = fewest[colour].max(count);
+            }
+            generated.part_two = checked_power(id, fewest)?
+                .checked_add(generated.part_two)
+                .ok_or(Error::TooLarge(id))?;
+
+            let draws: Vec<String> = draws
+                .iter()
+                .map(|draw| {
+                    let counts: Vec<String> = draw
+                        .iter()
+                        .map(|&(colour, count)| format!("{count} {}", colours[colour]))
+                        .collect();
+                    counts.join(", ")
+                })
+                .collect();
+            generated
+                .input
+                .push_str(&format!("Game {id}: {}\n", draws.join("; ")));
+        }
+
+        Ok(generated)
+    }
+}
+
+/// Takes a random number of cubes out of the bag, and the count of each
+/// colour drawn in a random order.
+fn draw(rng: &mut Rng, cubes: &mut [usize], colours: usize) -> Vec<(usize, i32)> {
+    let taken = rng.usize(1..=cubes.len());
+    // Partial Fisher-Yates shuffle, the first `taken` cubes are the draw
+    for i in 0..taken {
+        let j = rng.usize(i..cubes.len());
+        cubes.swap(i, j);
+    }
+
+    let mut counts = vec![0; colours];
+    for &colour in &cubes[..taken] {
+        counts[colour] += 1;
+    }
+    let mut drawn: Vec<(usize, i32)> = counts
+        .into_iter()
+        .enumerate()
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    rng.shuffle(&mut drawn);
+
+    drawn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day2;
+    use common::Solution;
+
+    #[test]
+    fn answers_and_labels_match_the_solver() {
+        let generator = Generator {
+            games: 300,
+            bag: "red=12,green=13,blue=14,yellow=2".parse().unwrap(),
+            impossible: 0.3,
+        };
+        let day = Day2 {
+            bag: generator.bag.clone(),
+            ..Day2::default()
+        };
+        for seed in 0..10 {
+            let generated = generator.generate(seed).unwrap();
+            let games = day.parse(&generated.input).unwrap();
+            assert_eq!(day.part_one(&games), Ok(generated.part_one));
+            assert_eq!(day.part_two(&games), Ok(generated.part_two));
+
+            let impossible: Vec<i32> = games
+                .iter()
+                .filter(|game| !game.is_possible(&day.bag))
+                .map(|game| game.id)
+                .collect();
+            assert_eq!(impossible, generated.impossible);
+        }
+
+        assert_eq!(generator.generate(3), generator.generate(3));
+        assert_ne!(generator.generate(3), generator.generate(4));
+
+        let generator = Generator {
+            games: 1000,
+            bag: (0..16).map(|i| (format!("c{i}"), 6000)).collect(),
+            impossible: 0.0,
+        };
+        assert!(matches!(generator.generate(0), Err(Error::TooLarge(_))));
+    }
+
+    #[test]
+    fn answers_of_long_logs_go_past_i32() {
+        let generator = Generator {
+            games: 70_000,
+            bag: Bag::default(),
+            impossible: 0.0,
+        };
+        let generated = generator.generate(0).unwrap();
+        assert!(generated.part_one > i32::MAX as i64);
+
+        let day = Day2::default();
+        let games = day.parse(&generated.input).unwrap();
+        assert_eq!(day.part_one(&games), Ok(generated.part_one));
+        assert_eq!(day.part_two(&games), Ok(generated.part_two));
+    }
+}
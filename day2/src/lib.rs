@@ -0,0 +1,374 @@
+mod bag;
+mod diagnose;
+mod export;
+mod generate;
+mod optimise;
+mod stats;
+mod stream;
+
+pub use bag::{Bag, BagError};
+pub use diagnose::{Diagnosis, Raise, Violation};
+pub use export::Export;
+pub use generate::{Generated, Generator};
+pub use optimise::BestBag;
+pub use stats::{Bucket, Stats, Summary};
+pub use stream::{StreamError, Totals};
+
+use clap::ValueEnum;
+use common::{Location, Solution};
+use log::trace;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("expected \"Game <id>:\" at {0}")]
+    MissingId(Location),
+    #[error("invalid number at {0}")]
+    InvalidNumber(Location),
+    #[error("missing colour after the number of cubes at {0}")]
+    MissingColour(Location),
+    #[error("empty draw at {0}")]
+    EmptyDraw(Location),
+    #[error("game {game} draws {colour} cubes but the bag does not list {colour}")]
+    UnlistedColour { game: i32, colour: String },
+    #[error("there is no game {0}")]
+    MissingGame(i32),
+    #[error("answer does not fit in 64 bits at game {0}")]
+    TooLarge(i32),
+}
+
+/// Product of the fewest cubes of each colour game `id` needs.
+fn checked_power(id: i32, fewest: impl IntoIterator<Item = i32>) -> Result<i64, Error> {
+    fewest
+        .into_iter()
+        .try_fold(1_i64, |power, count| power.checked_mul(count as i64))
+        .ok_or(Error::TooLarge(id))
+}
+
+/// Id of the game in the `line_index` line and the text of its draws.
+fn split_game(line_index: usize, line: &str) -> Result<(i32, &str), Error> {
+    let location = |text: &str| Location::new(line_index, line, text);
+    let (game_str, withdraws_str) = line
+        .split_once(':')
+        .ok_or_else(|| Error::MissingId(location(line)))?;
+    let id_str = game_str
+        .strip_prefix("Game ")
+        .ok_or_else(|| Error::MissingId(location(game_str)))?;
+    let id = id_str
+        .parse::<i32>()
+        .map_err(|_| Error::InvalidNumber(location(id_str)))?;
+
+    Ok((id, withdraws_str))
+}
+
+/// Colour and count of each entry of one draw, borrowed from the line.
+/// `draw` is a slice of `line` so errors can point at the right column.
+fn parse_draw<'a>(
+    line_index: usize,
+    line: &'a str,
+    draw: &'a str,
+) -> impl Iterator<Item = Result<(&'a str, i32), Error>> + 'a {
+    draw.split(',').map(move |cube| {
+        let location = |text: &str| Location::new(line_index, line, text);
+        let cube = cube.trim();
+        let mut input = cube.split_whitespace();
+        let number_str = input
+            .next()
+            .ok_or_else(|| Error::EmptyDraw(location(cube)))?;
+        let number = number_str
+            .parse::<i32>()
+            .map_err(|_| Error::InvalidNumber(location(number_str)))?;
+        let colour = input
+            .next()
+            .ok_or_else(|| Error::MissingColour(location(cube)))?;
+
+        Ok((colour, number))
+    })
+}
+
+/// Cubes of each colour in one draw, in the order they were written.
+#[derive(Debug, Clone, Default)]
+struct Cubes {
+    cubes: Vec<(String, i32)>,
+}
+
+impl Cubes {
+    /// Parses one draw of the game in the `line_index` line, `game` being a
+    /// slice of `line` so errors can point at the right column.
+    fn new(line_index: usize, line: &str, game: &str) -> Result<Cubes, Error> {
+        let mut out = Cubes::default();
+        for cube in parse_draw(line_index, line, game) {
+            let (colour, number) = cube?;
+            out.set(colour, number);
+        }
+
+        Ok(out)
+    }
+
+    /// Cubes of `colour`, 0 when there are none.
+    fn get(&self, colour: &str) -> i32 {
+        self.cubes
+            .iter()
+            .find(|(listed, _)| listed == colour)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Sets the cubes of `colour`, the last count wins when a colour is
+    /// written twice in a draw.
+    fn set(&mut self, colour: &str, count: i32) {
+        match self.cubes.iter_mut().find(|(listed, _)| listed == colour) {
+            Some((_, listed)) => *listed = count,
+            None => self.cubes.push((colour.to_string(), count)),
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&str, i32)> {
+        self.cubes
+            .iter()
+            .map(|(colour, count)| (colour.as_str(), *count))
+    }
+
+    /// Colours the bag does not list that were drawn at least once.
+    fn unlisted<'a>(&'a self, bag: &'a Bag) -> impl Iterator<Item = &'a str> {
+        self.iter()
+            .filter(|(colour, count)| *count > 0 && bag.get(colour).is_none())
+            .map(|(colour, _)| colour)
+    }
+
+    /// Whether the draw fits in `bag`, colours the bag does not list have no
+    /// cubes.
+    fn is_possible(&self, bag: &Bag) -> bool {
+        self.iter()
+            .all(|(colour, count)| count <= bag.get(colour).unwrap_or(0))
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    id: i32,
+    withdraws: Vec<Cubes>,
+}
+
+impl Game {
+    fn new(line_index: usize, line: &str) -> Result<Game, Error> {
+        let (id, withdraws_str) = split_game(line_index, line)?;
+        let withdraws_strs = withdraws_str.split(';');
+        let mut withdraws: Vec<Cubes> = vec![];
+        for withdraw_str in withdraws_strs {
+            withdraws.push(Cubes::new(line_index, line, withdraw_str)?);
+        }
+
+        Ok(Game { id, withdraws })
+    }
+
+    fn is_possible(&self, bag: &Bag) -> bool {
+        for withdraw in self.withdraws.iter() {
+            if !withdraw.is_possible(bag) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn min_cubes(&self) -> Cubes {
+        let mut out = Cubes::default();
+        for withdraw in self.withdraws.iter() {
+            for (colour, count) in withdraw.iter() {
+                if count > out.get(colour) {
+                    out.set(colour, count);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// What to do with games drawing a colour the bag does not list.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OnUnlisted {
+    /// The bag has none of those cubes, so the game is impossible
+    #[default]
+    Impossible,
+    /// Stop with an error naming the game and the colour
+    Error,
+}
+
+#[derive(Default)]
+pub struct Day2 {
+    /// Bag the games of part one are checked against, its colours are the
+    /// ones multiplied for the power in part two
+    pub bag: Bag,
+    pub on_unlisted: OnUnlisted,
+}
+
+impl Day2 {
+    fn is_possible(&self, game: &Game, bag: &Bag) -> Result<bool, Error> {
+        if self.on_unlisted == OnUnlisted::Error {
+            let unlisted = game
+                .withdraws
+                .iter()
+                .find_map(|withdraw| withdraw.unlisted(bag).next());
+            if let Some(colour) = unlisted {
+                return Err(Error::UnlistedColour {
+                    game: game.id,
+                    colour: colour.to_string(),
+                });
+            }
+        }
+
+        Ok(game.is_possible(bag))
+    }
+
+    /// Sum of the ids of the games that could be played with `bag`, wide
+    /// enough for logs of millions of games.
+    pub fn id_sum(&self, games: &[Game], bag: &Bag) -> Result<i64, Error> {
+        let mut total = 0;
+        for game in games {
+            if !self.is_possible(game, bag)? {
+                trace!("game {} is not possible with {bag}", game.id);
+                continue;
+            }
+            total = (game.id as i64)
+                .checked_add(total)
+                .ok_or(Error::TooLarge(game.id))?;
+        }
+
+        Ok(total)
+    }
+
+    /// Product of the fewest cubes of each colour of the bag the game needs.
+    fn power(&self, game: &Game) -> Result<i64, Error> {
+        let min_cubes = game.min_cubes();
+        checked_power(
+            game.id,
+            self.bag.colours().map(|colour| min_cubes.get(colour)),
+        )
+    }
+}
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Game>;
+    type Answer = i64;
+    type Error = Error;
+
+    fn parse(&self, input: &str) -> Result<Vec<Game>, Error> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| Game::new(i, line))
+            .collect()
+    }
+
+    fn part_one(&self, games: &Vec<Game>) -> Result<i64, Error> {
+        self.id_sum(games, &self.bag)
+    }
+
+    fn part_two(&self, games: &Vec<Game>) -> Result<i64, Error> {
+        let mut total = 0;
+        for game in games {
+            total = self
+                .power(game)?
+                .checked_add(total)
+                .ok_or(Error::TooLarge(game.id))?;
+        }
+
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part_one_example() {
+        assert_eq!(
+            Day2::default().part_one(&Day2::default().parse(EXAMPLE).unwrap()),
+            Ok(8)
+        );
+    }
+
+    #[test]
+    fn part_two_example() {
+        assert_eq!(
+            Day2::default().part_two(&Day2::default().parse(EXAMPLE).unwrap()),
+            Ok(2286)
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_text() {
+        let error = Day2::default()
+            .parse("Game 1: 3 blue\nGame 2: 3 blue, x red")
+            .unwrap_err();
+        assert_eq!(
+            error,
+            Error::InvalidNumber(Location {
+                line: 2,
+                column: 17,
+                text: "x".to_string(),
+            })
+        );
+
+        let error = Day2::default().parse("Gme 1: 3 blue").unwrap_err();
+        assert!(matches!(error, Error::MissingId(_)));
+    }
+
+    #[test]
+    fn bigger_bags_allow_more_games() {
+        let day = Day2::default();
+        let games = day.parse(EXAMPLE).unwrap();
+        let bag: Bag = "red=20,green=13,blue=15".parse().unwrap();
+        assert_eq!(day.id_sum(&games, &bag), Ok(1 + 2 + 3 + 4 + 5));
+        let bag: Bag = "red=12,green=13".parse().unwrap();
+        assert_eq!(day.id_sum(&games, &bag), Ok(0));
+    }
+
+    #[test]
+    fn colours_are_not_limited_to_red_green_and_blue() {
+        let log = "Game 1: 4 yellow, 1 red; 2 yellow\nGame 2: 3 red; 0 yellow";
+        let day = Day2::default();
+        let games = day.parse(log).unwrap();
+        assert_eq!(day.id_sum(&games, &day.bag), Ok(2));
+        let bag: Bag = "red=12,yellow=4".parse().unwrap();
+        assert_eq!(day.id_sum(&games, &bag), Ok(1 + 2));
+
+        let strict = Day2 {
+            on_unlisted: OnUnlisted::Error,
+            ..Day2::default()
+        };
+        assert_eq!(
+            strict.id_sum(&games, &strict.bag),
+            Err(Error::UnlistedColour {
+                game: 1,
+                colour: "yellow".to_string(),
+            })
+        );
+
+        let day = Day2 {
+            bag,
+            ..Day2::default()
+        };
+        assert_eq!(day.part_two(&games), Ok(4));
+    }
+
+    #[test]
+    fn answers_too_large_for_64_bits_are_errors() {
+        let day = Day2::default();
+        let games = day
+            .parse("Game 1: 1 red\nGame 2: 3000000 red, 3000000 green, 3000000 blue")
+            .unwrap();
+        assert_eq!(day.part_one(&games), Ok(1));
+        assert_eq!(day.part_two(&games), Err(Error::TooLarge(2)));
+
+        let games = day
+            .parse("Game 1: 3000000 red, 3000000 green, 1000000 blue\nGame 2: 1000000 red, 3000000 green, 3000000 blue")
+            .unwrap();
+        assert_eq!(day.part_two(&games), Err(Error::TooLarge(2)));
+    }
+}
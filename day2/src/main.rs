@@ -1,124 +1,218 @@
-use clap::{arg, command, Parser};
-use std::collections::HashMap;
-use std::fs;
+use clap::{Parser, Subcommand};
+use common::{Format, Part, Solution};
+use day2::{Bag, Day2, Export, Game, OnUnlisted};
+use log::debug;
+use serde::Serialize;
+use std::time::Instant;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-struct Args {
-    /// Part of the problem we want to solve 1 or 2, 1 by default
-    #[arg(short, long, default_value_t = 1)]
-    part: u8,
+struct Cli {
+    #[command(flatten)]
+    common: common::Args,
 
-    /// Path to the file with the input
-    #[arg(short, long)]
-    input: String,
-}
+    /// Bag for part one, like red=12,green=13,blue=14, or a file with one
+    /// bag per line. Repeat it to get the answers of every bag. Part two
+    /// multiplies the colours of the bag, so leaving one out changes it too
+    #[arg(long)]
+    bag: Vec<String>,
 
-struct Cubes {
-    blue: i32,
-    green: i32,
-    red: i32,
-}
+    /// What to do with games drawing a colour the bag does not list
+    #[arg(long, value_enum, default_value_t = OnUnlisted::Impossible)]
+    unlisted_colour: OnUnlisted,
 
-impl Cubes {
-    fn new(game: String) -> Cubes {
-        let cubes = game.split(",");
-        let mut game_map: HashMap<&str, i32> = HashMap::new();
-        for cube in cubes {
-            let mut input = cube.trim().split(" ");
-            let number = input.next().unwrap().parse::<i32>().unwrap();
-            let colour = input.next().unwrap();
-            game_map.insert(colour, number);
-        }
+    /// Show which draws make each game impossible and which games a single
+    /// bigger colour would allow, instead of the answers
+    #[arg(long, conflicts_with = "format")]
+    diagnose: bool,
 
-        Cubes {
-            blue: *game_map.get("blue").unwrap_or(&0),
-            green: *game_map.get("green").unwrap_or(&0),
-            red: *game_map.get("red").unwrap_or(&0),
-        }
-    }
+    /// Print every game with its draws, fewest cubes and power instead of
+    /// the answers
+    #[arg(long, value_enum, conflicts_with_all = ["format", "diagnose"])]
+    export: Option<Export>,
 
-    fn is_possible(&self, max_blue: i32, max_green: i32, max_red: i32) -> bool {
-        return self.blue <= max_blue && self.green <= max_green && self.red <= max_red;
-    }
+    /// Read the games line by line in constant memory, for huge logs
+    #[arg(long, conflicts_with_all = ["format", "diagnose", "export"])]
+    stream: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-struct Game {
-    id: i32,
-    withdraws: Vec<Cubes>,
+/// Look at the games as a whole instead of checking them against a bag.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Find the smallest bag with which every chosen game is possible
+    SmallestBag {
+        /// Ids of the games, separated by commas
+        #[arg(long, required = true, value_delimiter = ',')]
+        games: Vec<i32>,
+    },
+    /// Find the bag of at most this many cubes allowing the most games
+    BestBag {
+        /// Cubes of every colour together the bag can have
+        #[arg(long)]
+        budget: u32,
+    },
+    /// Summarise the log: cubes per draw of each colour, draws per game and
+    /// the spread of powers
+    Stats,
 }
 
-impl Game {
-    fn new(line: String) -> Game {
-        let mut id_and_withdraws = line.split(":");
-        let id = id_and_withdraws
-            .next()
-            .unwrap()
-            .to_string()
-            .strip_prefix("Game ")
-            .unwrap()
-            .parse::<i32>()
-            .unwrap();
-        let withdraws_strs = id_and_withdraws.next().unwrap().split(";");
-        let mut withdraws: Vec<Cubes> = vec![];
-        for withdraw_str in withdraws_strs {
-            withdraws.push(Cubes::new(withdraw_str.to_string()));
-        }
+fn read_games(day: &Day2, args: &common::Args) -> Vec<Game> {
+    args.verbosity.init();
+    let input = common::read_input(&args.input)
+        .unwrap_or_else(|error| common::exit_with(format!("{}: {error}", args.input)));
+    day.parse(&input)
+        .unwrap_or_else(|error| common::exit_with(error))
+}
 
-        Game {
-            id: id,
-            withdraws: withdraws,
+/// Answer of one part with one of several bags, what `--format json` prints
+/// when comparing them.
+#[derive(Serialize)]
+struct BagReport {
+    day: u8,
+    bag: String,
+    part: u8,
+    answer: String,
+}
+
+/// Prints the answers of every bag, parsing the games only once. Part two
+/// multiplies the colours of each bag.
+fn compare_bags(day: &Day2, bags: &[Bag], args: &common::Args) {
+    let games = read_games(day, args);
+    for bag in bags {
+        let day = Day2 {
+            bag: bag.clone(),
+            on_unlisted: day.on_unlisted,
+        };
+        let mut answers = vec![];
+        for &part in args.part.numbers() {
+            let answer = match part {
+                1 => day.part_one(&games),
+                _ => day.part_two(&games),
+            };
+            let answer = answer.unwrap_or_else(|error| common::exit_with(error));
+            answers.push((part, answer.to_string()));
         }
-    }
 
-    fn is_possible(&self, max_blue: i32, max_green: i32, max_red: i32) -> bool {
-        for withdraw in self.withdraws.iter() {
-            if !withdraw.is_possible(max_blue, max_green, max_red) {
-                return false;
+        if args.format == Format::Json {
+            for (part, answer) in answers {
+                let report = BagReport {
+                    day: Day2::DAY,
+                    bag: bag.to_string(),
+                    part,
+                    answer,
+                };
+                println!("{}", serde_json::to_string(&report).unwrap());
             }
+            continue;
         }
+        let answers: Vec<String> = match args.part {
+            Part::Both => answers
+                .iter()
+                .map(|(part, answer)| format!("part {part} {answer}"))
+                .collect(),
+            _ => answers.into_iter().map(|(_, answer)| answer).collect(),
+        };
+        println!("{bag}: {}", answers.join(", "));
+    }
+}
 
-        true
+fn diagnose(day: &Day2, bags: &[Bag], args: &common::Args) {
+    let games = read_games(day, args);
+    for bag in bags {
+        print!("{}", day.diagnose(&games, bag));
     }
+}
 
-    fn min_cubes(&self) -> Cubes {
-        let mut out = Cubes {
-            blue: 0,
-            green: 0,
-            red: 0,
-        };
-        for withdraw in self.withdraws.iter() {
-            if withdraw.blue > out.blue {
-                out.blue = withdraw.blue;
-            }
-            if withdraw.green > out.green {
-                out.green = withdraw.green;
-            }
-            if withdraw.red > out.red {
-                out.red = withdraw.red;
-            }
+fn run_command(day: &Day2, command: &Command, args: &common::Args) {
+    let games = read_games(day, args);
+    match command {
+        Command::SmallestBag { games: ids } => {
+            let bag = day
+                .smallest_bag(&games, ids)
+                .unwrap_or_else(|error| common::exit_with(error));
+            println!("{bag} ({} cubes)", bag.total());
+        }
+        Command::BestBag { budget } => {
+            let best = day.best_bag(&games, *budget);
+            let ids: Vec<String> = best.games.iter().map(|id| id.to_string()).collect();
+            println!(
+                "{} ({} cubes) allows {} games: {}",
+                best.bag,
+                best.bag.total(),
+                ids.len(),
+                ids.join(", ")
+            );
+        }
+        Command::Stats => {
+            let stats = day
+                .stats(&games)
+                .unwrap_or_else(|error| common::exit_with(error));
+            print!("{stats}");
         }
-        out
     }
 }
 
+/// Answers the parts one game at a time, for logs too big to parse into
+/// `Game`s first.
+fn stream_input(day: &Day2, args: &common::Args) {
+    args.verbosity.init();
+    let reader = common::open_input(&args.input)
+        .unwrap_or_else(|error| common::exit_with(format!("{}: {error}", args.input)));
+
+    let start = Instant::now();
+    let totals = day
+        .stream(reader, args.part.numbers())
+        .unwrap_or_else(|error| common::exit_with(error));
+    debug!("streamed the input in {:?}", start.elapsed());
+
+    common::print_answers(args, [totals.part_one, totals.part_two]);
+}
+
 fn main() {
-    let args = Args::parse();
-    let file_content =
-        fs::read_to_string(args.input).expect("Should have been able to read the file");
-    let lines = file_content.lines();
-    let mut total = 0;
-    for line in lines {
-        let game = Game::new(line.to_string());
-        if args.part == 2 {
-            total += game.min_cubes().red * game.min_cubes().green * game.min_cubes().blue;
-        } else {
-            if !game.is_possible(14, 13, 12) {
-                continue;
-            }
-            total += game.id;
-        }
+    let args = Cli::parse();
+    let mut bags = vec![];
+    for bag in &args.bag {
+        bags.extend(Bag::load(bag).unwrap_or_else(|error| common::exit_with(error)));
     }
 
-    println!("{total}");
+    let mut day = Day2 {
+        on_unlisted: args.unlisted_colour,
+        ..Day2::default()
+    };
+    if let [bag] = bags.as_slice() {
+        day.bag = bag.clone();
+    }
+    if let Some(command) = &args.command {
+        run_command(&day, command, &args.common);
+        return;
+    }
+    if let Some(format) = args.export {
+        let games = read_games(&day, &args.common);
+        let export = day
+            .export(&games, format)
+            .unwrap_or_else(|error| common::exit_with(error));
+        print!("{export}");
+        return;
+    }
+    if args.diagnose {
+        if bags.is_empty() {
+            bags.push(day.bag.clone());
+        }
+        diagnose(&day, &bags, &args.common);
+        return;
+    }
+    if args.stream {
+        if bags.len() > 1 {
+            common::exit_with("--stream only solves the parts, with a single bag");
+        }
+        stream_input(&day, &args.common);
+        return;
+    }
+    match bags.len() {
+        0 | 1 => common::run(&day, &args.common),
+        _ => compare_bags(&day, &bags, &args.common),
+    }
 }
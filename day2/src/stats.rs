@@ -0,0 +1,198 @@
+use crate::{Day2, Error, Game};
+use std::fmt::{self, Display};
+
+/// Buckets in the histogram of powers.
+const POWER_BUCKETS: i64 = 10;
+/// Width of the longest bar of the histogram.
+const BAR_WIDTH: usize = 40;
+
+/// How a set of counts is spread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub min: i32,
+    /// Middle value, the lower one of the two middle values for an even
+    /// count
+    pub median: i32,
+    pub mean: f64,
+    pub max: i32,
+}
+
+impl Summary {
+    /// None when there are no values.
+    fn of(mut values: Vec<i32>) -> Option<Summary> {
+        values.sort_unstable();
+        let (&min, &max) = (values.first()?, values.last()?);
+        let sum: i64 = values.iter().map(|&value| value as i64).sum();
+
+        Some(Summary {
+            count: values.len(),
+            min,
+            median: values[(values.len() - 1) / 2],
+            mean: sum as f64 / values.len() as f64,
+            max,
+        })
+    }
+}
+
+/// Games with a power from `from` to `to`, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
+    pub from: i64,
+    pub to: i64,
+    pub games: usize,
+}
+
+/// What a game log looks like, to sanity-check generated ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub games: usize,
+    /// Cubes of each colour taken in the draws showing it, None for a
+    /// colour of the bag no draw shows
+    pub colours: Vec<(String, Option<Summary>)>,
+    pub draws: Option<Summary>,
+    /// Id and power of the first game with the highest power
+    pub highest_power: Option<(i32, i64)>,
+    pub powers: Vec<Bucket>,
+}
+
+fn histogram(powers: &[i64]) -> Vec<Bucket> {
+    let (Some(&min), Some(&max)) = (powers.iter().min(), powers.iter().max()) else {
+        return vec![];
+    };
+    // Wide enough for the range to fit in at most POWER_BUCKETS buckets
+    let width = (max - min) / POWER_BUCKETS + 1;
+    let mut buckets: Vec<Bucket> = (0..=(max - min) / width)
+        .map(|i| Bucket {
+            from: min + i * width,
+            // The last bucket may end past i64::MAX
+            to: (min + i * width).saturating_add(width - 1),
+            games: 0,
+        })
+        .collect();
+    for power in powers {
+        buckets[((power - min) / width) as usize].games += 1;
+    }
+
+    buckets
+}
+
+impl Day2 {
+    pub fn stats(&self, games: &[Game]) -> Result<Stats, Error> {
+        let colours = self
+            .colours(games)
+            .into_iter()
+            .map(|colour| {
+                let counts = games
+                    .iter()
+                    .flat_map(|game| &game.withdraws)
+                    .flat_map(|withdraw| withdraw.iter())
+                    .filter(|(drawn, _)| *drawn == colour)
+                    .map(|(_, count)| count)
+                    .collect();
+                (colour, Summary::of(counts))
+            })
+            .collect();
+        let draws = games.iter().map(|game| game.withdraws.len() as i32);
+        let powers = games
+            .iter()
+            .map(|game| self.power(game))
+            .collect::<Result<Vec<i64>, Error>>()?;
+        let highest_power = games
+            .iter()
+            .zip(&powers)
+            .rev()
+            .max_by_key(|(_, power)| **power)
+            .map(|(game, power)| (game.id, *power));
+
+        Ok(Stats {
+            games: games.len(),
+            colours,
+            draws: Summary::of(draws.collect()),
+            highest_power,
+            powers: histogram(&powers),
+        })
+    }
+}
+
+fn row(f: &mut fmt::Formatter<'_>, name: &str, summary: &Option<Summary>) -> fmt::Result {
+    match summary {
+        Some(s) => writeln!(
+            f,
+            "{name:<16} {:>7} {:>5} {:>7} {:>7.2} {:>5}",
+            s.count, s.min, s.median, s.mean, s.max
+        ),
+        None => writeln!(
+            f,
+            "{name:<16} {:>7} {:>5} {:>7} {:>7} {:>5}",
+            0, "-", "-", "-", "-"
+        ),
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} games", self.games)?;
+        writeln!(
+            f,
+            "\n{:<16} {:>7} {:>5} {:>7} {:>7} {:>5}",
+            "", "count", "min", "median", "mean", "max"
+        )?;
+        for (colour, summary) in &self.colours {
+            row(f, &format!("{colour} per draw"), summary)?;
+        }
+        row(f, "draws per game", &self.draws)?;
+
+        if let Some((id, power)) = self.highest_power {
+            writeln!(f, "\nHighest power: {power} in game {id}")?;
+        }
+        if !self.powers.is_empty() {
+            writeln!(f, "\n{:>15} {:>7}", "power", "games")?;
+        }
+        let most = self.powers.iter().map(|bucket| bucket.games).max();
+        for bucket in &self.powers {
+            let bar = "#".repeat(bucket.games * BAR_WIDTH / most.unwrap().max(1));
+            let range = format!("{}-{}", bucket.from, bucket.to);
+            let line = format!("{range:>15} {:>7} {bar}", bucket.games);
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn summarises_the_example() {
+        let day = Day2::default();
+        let games = day.parse(EXAMPLE).unwrap();
+        let stats = day.stats(&games).unwrap();
+
+        assert_eq!(stats.games, 5);
+        assert_eq!(
+            stats.colours[0],
+            (
+                "red".to_string(),
+                Some(Summary {
+                    count: 11,
+                    min: 1,
+                    median: 4,
+                    mean: 61.0 / 11.0,
+                    max: 20,
+                })
+            )
+        );
+        assert_eq!(stats.draws.as_ref().map(|draws| draws.max), Some(3));
+        assert_eq!(stats.highest_power, Some((3, 1560)));
+        // Powers 48, 12, 1560, 630 and 36, in buckets 155 wide from 12
+        let games: Vec<usize> = stats.powers.iter().map(|bucket| bucket.games).collect();
+        assert_eq!(games, vec![3, 0, 0, 1, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(stats.powers[0].to, 166);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{checked_power, parse_draw, split_game, Day2, Error, OnUnlisted};
+use log::trace;
+use std::io::{self, BufRead};
+
+#[derive(thiserror::Error, Debug)]
+pub enum StreamError {
+    #[error("could not read the input: {0}")]
+    Read(#[from] io::Error),
+    #[error(transparent)]
+    Game(#[from] Error),
+}
+
+/// Answers of the parts that were asked for, None for the others.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    pub part_one: Option<i64>,
+    pub part_two: Option<i64>,
+}
+
+/// One game read straight from its line.
+struct Scanned<'a> {
+    id: i32,
+    /// Whether every draw fits in the bag
+    possible: bool,
+    /// First colour drawn that the bag does not list
+    unlisted: Option<&'a str>,
+}
+
+/// Buffers kept from game to game, one entry per colour of the bag.
+struct Scratch {
+    /// Cubes of each colour in the bag
+    limits: Vec<i32>,
+    /// Count of each colour in the current draw, if it was written
+    draw: Vec<Option<i32>>,
+    /// Fewest cubes of each colour the current game needs
+    fewest: Vec<i32>,
+}
+
+impl Day2 {
+    /// Checks the game in `line` against the bag as it parses it, leaving
+    /// in `scratch.fewest` the fewest cubes of each colour of the bag it
+    /// needs. Nothing is allocated unless the line is invalid or draws
+    /// colours the bag does not list.
+    fn scan<'a>(
+        &self,
+        line_index: usize,
+        line: &'a str,
+        scratch: &mut Scratch,
+    ) -> Result<Scanned<'a>, Error> {
+        let (id, withdraws_str) = split_game(line_index, line)?;
+        scratch.fewest.fill(0);
+        let mut game = Scanned {
+            id,
+            possible: true,
+            unlisted: None,
+        };
+        for withdraw in withdraws_str.split(';') {
+            // The last count wins when a colour is written twice in a draw,
+            // so the counts are only checked once the draw is over
+            scratch.draw.fill(None);
+            let mut unlisted: Vec<(&str, i32)> = vec![];
+            for cube in parse_draw(line_index, line, withdraw) {
+                let (colour, number) = cube?;
+                match self.bag.colours().position(|listed| listed == colour) {
+                    Some(index) => scratch.draw[index] = Some(number),
+                    None => match unlisted.iter_mut().find(|(listed, _)| *listed == colour) {
+                        Some((_, count)) => *count = number,
+                        None => unlisted.push((colour, number)),
+                    },
+                }
+            }
+
+            for (index, count) in scratch.draw.iter().enumerate() {
+                if let Some(count) = *count {
+                    game.possible &= count <= scratch.limits[index];
+                    scratch.fewest[index] = scratch.fewest[index].max(count);
+                }
+            }
+            for (colour, count) in unlisted {
+                if count > 0 {
+                    game.possible = false;
+                    game.unlisted.get_or_insert(colour);
+                }
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Solves `parts` checking each game against the bag while it is parsed,
+    /// borrowing from the line instead of building a `Game`. Memory stays
+    /// flat however many games the log has.
+    pub fn stream(&self, mut reader: impl BufRead, parts: &[u8]) -> Result<Totals, StreamError> {
+        let limits: Vec<i32> = self
+            .bag
+            .colours()
+            .map(|colour| self.bag.get(colour).unwrap())
+            .collect();
+        let mut scratch = Scratch {
+            draw: vec![None; limits.len()],
+            fewest: vec![0; limits.len()],
+            limits,
+        };
+        let (mut id_sum, mut power_sum) = (0, 0);
+
+        let mut buffer = String::new();
+        let mut line_index = 0;
+        while reader.read_line(&mut buffer)? > 0 {
+            // Only ASCII is trimmed, so the rest ends on a character boundary
+            let line = &buffer[..common::trim_newline(buffer.as_bytes()).len()];
+            let game = self.scan(line_index, line, &mut scratch)?;
+            // Only part one checks the games against the bag
+            if let (Some(colour), OnUnlisted::Error, true) =
+                (game.unlisted, self.on_unlisted, parts.contains(&1))
+            {
+                return Err(Error::UnlistedColour {
+                    game: game.id,
+                    colour: colour.to_string(),
+                }
+                .into());
+            }
+            let too_large = || Error::TooLarge(game.id);
+            if !game.possible {
+                trace!("game {} is not possible with {}", game.id, self.bag);
+            } else if parts.contains(&1) {
+                id_sum = (game.id as i64).checked_add(id_sum).ok_or_else(too_large)?;
+            }
+            if parts.contains(&2) {
+                power_sum = checked_power(game.id, scratch.fewest.iter().copied())?
+                    .checked_add(power_sum)
+                    .ok_or_else(too_large)?;
+            }
+
+            buffer.clear();
+            line_index += 1;
+        }
+
+        Ok(Totals {
+            part_one: parts.contains(&1).then_some(id_sum),
+            part_two: parts.contains(&2).then_some(power_sum),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Generator;
+    use common::Location;
+    use common::Solution;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    /// Answers of the solver going through `Game`, to compare with.
+    fn solve(day: &Day2, input: &str) -> Totals {
+        let games = day.parse(input).unwrap();
+        Totals {
+            part_one: Some(day.part_one(&games).unwrap()),
+            part_two: Some(day.part_two(&games).unwrap()),
+        }
+    }
+
+    #[test]
+    fn gives_the_answers_of_the_solver() {
+        let day = Day2::default();
+        let totals = day.stream(EXAMPLE.as_bytes(), &[1, 2]).unwrap();
+        assert_eq!(
+            totals,
+            Totals {
+                part_one: Some(8),
+                part_two: Some(2286),
+            }
+        );
+
+        // Colours written twice in a draw, colours the bag does not list and
+        // Windows line endings
+        let input = "Game 1: 13 red, 2 red; 1 blue\r\n\
+                     Game 2: 0 yellow, 3 green\r\n\
+                     Game 3: 1 yellow; 5 blue, 4 blue\r\n\
+                     Game 4: 2 pink, 0 pink, 1 red, 15 blue, 1 blue\r\n";
+        assert_eq!(
+            day.stream(input.as_bytes(), &[1, 2]).unwrap(),
+            solve(&day, input)
+        );
+
+        let generator = Generator {
+            games: 500,
+            bag: "red=12,green=13,blue=14,yellow=2".parse().unwrap(),
+            impossible: 0.3,
+        };
+        let day = Day2 {
+            bag: generator.bag.clone(),
+            ..Day2::default()
+        };
+        let input = generator.generate(1).unwrap().input;
+        assert_eq!(
+            day.stream(input.as_bytes(), &[1, 2]).unwrap(),
+            solve(&day, &input)
+        );
+        assert_eq!(day.stream(input.as_bytes(), &[2]).unwrap().part_one, None);
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_text() {
+        let day = Day2 {
+            on_unlisted: OnUnlisted::Error,
+            ..Day2::default()
+        };
+        let error = day
+            .stream("Game 1: 1 red\nGame 2: 1 red, x blue\n".as_bytes(), &[1])
+            .unwrap_err();
+        let StreamError::Game(error) = error else {
+            panic!("expected a game error, found {error}");
+        };
+        assert_eq!(
+            error,
+            Error::InvalidNumber(Location {
+                line: 2,
+                column: 16,
+                text: "x".to_string(),
+            })
+        );
+
+        let error = day
+            .stream("Game 4: 1 red; 2 pink".as_bytes(), &[1])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "game 4 draws pink cubes but the bag does not list pink"
+        );
+
+        let input = "Game 1: 1 red\nGame 2: 3000000 red, 3000000 green, 3000000 blue\n";
+        let error = day.stream(input.as_bytes(), &[2]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "answer does not fit in 64 bits at game 2"
+        );
+        assert_eq!(
+            day.stream(input.as_bytes(), &[1]).unwrap().part_one,
+            Some(1)
+        );
+
+        // Part two alone never checks the bag, as without --stream
+        let input = "Game 4: 1 red; 2 pink";
+        let games = day.parse(input).unwrap();
+        assert_eq!(
+            day.stream(input.as_bytes(), &[2]).unwrap().part_two,
+            Some(day.part_two(&games).unwrap())
+        );
+    }
+}
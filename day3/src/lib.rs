@@ -0,0 +1,213 @@
+use common::{Location, Solution};
+use log::trace;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("row has a different width than the first one at {0}")]
+    UnevenRow(Location),
+    #[error("number too large at {0}")]
+    InvalidNumber(Location),
+}
+
+fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.'
+}
+
+fn is_gear(c: char) -> bool {
+    c == '*'
+}
+
+fn get_touching_parts(parts: &Vec<Number>, row: i32, column: i32) -> Vec<i32> {
+    let mut touching_parts: Vec<i32> = Vec::new();
+    for part in parts {
+        if part.is_adjacent(row, column) {
+            touching_parts.push(part.calculate_value());
+        }
+    }
+    touching_parts
+}
+
+struct Position {
+    row: i32,
+    start: i32,
+    end: i32,
+}
+struct Number {
+    position: Position,
+    chars: Vec<char>,
+}
+
+impl Number {
+    fn value(&self) -> Option<i32> {
+        let chars_to_string = self.chars.iter().collect::<String>();
+        chars_to_string.parse::<i32>().ok()
+    }
+
+    fn calculate_value(&self) -> i32 {
+        // Every number is checked to fit while parsing the schematic
+        self.value().unwrap()
+    }
+
+    fn is_part(&self, map: &HashMap<i32, Vec<char>>) -> bool {
+        for row_n in self.position.row - 1..(self.position.row + 2) {
+            let row_option = map.get(&row_n);
+            if row_option.is_none() {
+                continue;
+            }
+
+            let row = row_option.unwrap();
+            let mut start_index = self.position.start - 1;
+            if start_index < 0 {
+                start_index = 0;
+            }
+            let mut end_index = self.position.end + 1;
+            if end_index > row.len() as i32 {
+                end_index = row.len() as i32;
+            }
+            for n in start_index..end_index + 1 {
+                if is_symbol(row[n as usize]) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn is_adjacent(&self, row: i32, column: i32) -> bool {
+        row >= self.position.row - 1
+            && row <= self.position.row + 1
+            && column >= self.position.start - 1
+            && column <= self.position.end + 1
+    }
+}
+
+pub struct Schematic {
+    numbers: Vec<Number>,
+    map: HashMap<i32, Vec<char>>,
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Schematic;
+    type Answer = i32;
+    type Error = Error;
+
+    fn parse(&self, input: &str) -> Result<Schematic, Error> {
+        let lines = input.lines();
+        let mut schematic = Schematic {
+            numbers: Vec::new(),
+            map: HashMap::new(),
+        };
+        let mut width = None;
+        for (i, line) in lines.enumerate() {
+            let line_string = line.to_string();
+            let line_width = line_string.chars().count();
+            if *width.get_or_insert(line_width) != line_width {
+                return Err(Error::UnevenRow(Location::new(i, line, line)));
+            }
+
+            let mut row: Vec<char> = vec![];
+            let mut current_number: Option<Number> = None;
+            for (j, char) in line_string.chars().enumerate() {
+                row.push(char);
+                if char.is_ascii_digit() {
+                    match current_number.as_mut() {
+                        Some(number) => number.chars.push(char),
+                        None => {
+                            current_number = Some(Number {
+                                chars: vec![char],
+                                position: Position {
+                                    row: i as i32,
+                                    start: j as i32,
+                                    end: 0,
+                                },
+                            });
+                        }
+                    }
+                } else if current_number.is_some() {
+                    let number = current_number.as_mut().unwrap();
+                    number.position.end = (j - 1) as i32;
+                    if number.value().is_none() {
+                        return Err(Error::InvalidNumber(Location {
+                            line: i + 1,
+                            column: number.position.start as usize + 1,
+                            text: number.chars.iter().collect(),
+                        }));
+                    }
+
+                    schematic.numbers.push(current_number.unwrap());
+                    current_number = None;
+                }
+            }
+
+            schematic.map.insert(i as i32, row);
+        }
+
+        Ok(schematic)
+    }
+
+    fn part_one(&self, schematic: &Schematic) -> Result<i32, Error> {
+        let mut total = 0;
+        let numbers = &schematic.numbers;
+        for number in numbers {
+            if number.is_part(&schematic.map) {
+                total += number.calculate_value();
+            }
+        }
+
+        Ok(total)
+    }
+
+    fn part_two(&self, schematic: &Schematic) -> Result<i32, Error> {
+        let mut total = 0;
+        for (row_n, row) in schematic.map.iter() {
+            for (column_n, char) in row.iter().enumerate() {
+                if is_gear(*char) {
+                    let touching_parts =
+                        get_touching_parts(&schematic.numbers, *row_n, column_n as i32);
+                    trace!("gear at {row_n},{column_n} touches {:?}", touching_parts);
+                    if touching_parts.len() == 2 {
+                        total += touching_parts[0] * touching_parts[1];
+                    }
+                }
+            }
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part_one_example() {
+        assert_eq!(Day3.part_one(&Day3.parse(EXAMPLE).unwrap()), Ok(4361));
+    }
+
+    #[test]
+    fn part_two_example() {
+        assert_eq!(Day3.part_two(&Day3.parse(EXAMPLE).unwrap()), Ok(467835));
+    }
+
+    #[test]
+    fn rows_must_have_the_same_width() {
+        let error = Day3.parse("467..\n...*\n").err().unwrap();
+        assert_eq!(
+            error,
+            Error::UnevenRow(Location {
+                line: 2,
+                column: 1,
+                text: "...*".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use common::{Location, Solution};
+use log::trace;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("expected \"Card <id>:\" at {0}")]
+    MissingId(Location),
+    #[error("expected the winning numbers and the numbers split by \"|\" at {0}")]
+    MissingNumbers(Location),
+    #[error("invalid number at {0}")]
+    InvalidNumber(Location),
+    #[error("card {0} is missing")]
+    MissingCard(usize),
+}
+
+pub struct Card {
+    id: i32,
+    winning_numbers: HashSet<i32>,
+    numbers: HashSet<i32>,
+}
+fn get_card_id(line_index: usize, line: &str, card_str: &str) -> Result<i32, Error> {
+    let id_str = card_str
+        .strip_prefix("Card")
+        .ok_or_else(|| Error::MissingId(Location::new(line_index, line, card_str)))?
+        .trim();
+    id_str
+        .parse::<i32>()
+        .map_err(|_| Error::InvalidNumber(Location::new(line_index, line, id_str)))
+}
+
+fn get_numbers(line_index: usize, line: &str, numbers_str: &str) -> Result<HashSet<i32>, Error> {
+    let numbers_strs = numbers_str.split_whitespace();
+    let mut out: HashSet<i32> = HashSet::new();
+    for number_str in numbers_strs {
+        let number = number_str
+            .parse::<i32>()
+            .map_err(|_| Error::InvalidNumber(Location::new(line_index, line, number_str)))?;
+        out.insert(number);
+    }
+
+    Ok(out)
+}
+
+impl Card {
+    fn new(line_index: usize, line: &str) -> Result<Card, Error> {
+        let (card_str, both_cards) = line
+            .split_once(':')
+            .ok_or_else(|| Error::MissingId(Location::new(line_index, line, line)))?;
+        let card_id = get_card_id(line_index, line, card_str)?;
+        let (winning_numbers, numbers) = both_cards
+            .split_once('|')
+            .ok_or_else(|| Error::MissingNumbers(Location::new(line_index, line, both_cards)))?;
+
+        Ok(Card {
+            id: card_id,
+            winning_numbers: get_numbers(line_index, line, winning_numbers)?,
+            numbers: get_numbers(line_index, line, numbers)?,
+        })
+    }
+
+    fn points(&self) -> i32 {
+        let multiplier = 2;
+        let mut out = 0;
+        for number in &self.numbers {
+            if self.winning_numbers.contains(number) {
+                if out == 0 {
+                    out = 1
+                } else {
+                    out *= multiplier;
+                }
+            }
+        }
+
+        out
+    }
+
+    fn match_numbers(&self) -> i32 {
+        let mut out = 0;
+        for number in &self.numbers {
+            if self.winning_numbers.contains(number) {
+                out += 1;
+            }
+        }
+
+        out
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Card>;
+    type Answer = i32;
+    type Error = Error;
+
+    fn parse(&self, input: &str) -> Result<Vec<Card>, Error> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| Card::new(i, line))
+            .collect()
+    }
+
+    fn part_one(&self, cards: &Vec<Card>) -> Result<i32, Error> {
+        let mut out = 0;
+        for card in cards {
+            out += card.points();
+        }
+
+        Ok(out)
+    }
+
+    fn part_two(&self, cards: &Vec<Card>) -> Result<i32, Error> {
+        let mut out = 0;
+        let mut card_map: HashMap<usize, &Card> = HashMap::new();
+        let mut n_lines = 0;
+        let mut occurrences_map: HashMap<usize, i32> = HashMap::new();
+        for card in cards {
+            card_map.insert(card.id as usize, card);
+            n_lines += 1;
+        }
+
+        for n in 1..(n_lines + 1) {
+            let card = card_map.get(&n).ok_or(Error::MissingCard(n))?;
+            let n_occurrences = *occurrences_map.get(&n).unwrap_or(&1);
+            out += n_occurrences;
+            let card_match = card.match_numbers() as usize;
+            trace!("card {n}: {card_match} matches, {n_occurrences} copies");
+            for o in n + 1..(n + card_match + 1) {
+                if o > n_lines {
+                    continue;
+                }
+                let current_occurrences = occurrences_map.get(&o).unwrap_or(&1);
+                occurrences_map.insert(o, current_occurrences + n_occurrences);
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part_one_example() {
+        assert_eq!(Day4.part_one(&Day4.parse(EXAMPLE).unwrap()), Ok(13));
+    }
+
+    #[test]
+    fn part_two_example() {
+        assert_eq!(Day4.part_two(&Day4.parse(EXAMPLE).unwrap()), Ok(30));
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_text() {
+        let error = Day4.parse("Card 1: 41 4x | 83").err().unwrap();
+        assert_eq!(
+            error,
+            Error::InvalidNumber(Location {
+                line: 1,
+                column: 12,
+                text: "4x".to_string(),
+            })
+        );
+
+        let cards = Day4.parse("Card 1: 41 | 41\nCard 3: 1 | 2").unwrap();
+        assert_eq!(Day4.part_two(&cards), Err(Error::MissingCard(2)));
+    }
+}
@@ -0,0 +1,350 @@
+use common::{Location, Solution};
+use log::{debug, trace};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("expected the \"seeds:\" line at {0}")]
+    MissingSeeds(Location),
+    #[error("invalid number at {0}")]
+    InvalidNumber(Location),
+    #[error("expected \"<origin>-to-<destination> map:\" at {0}")]
+    InvalidMapHeader(Location),
+    #[error("range found before any map header at {0}")]
+    MissingMapHeader(Location),
+    #[error("expected destination, origin and range length at {0}")]
+    IncompleteMapLine(Location),
+    #[error("seed {0} has no range length to pair with")]
+    UnpairedSeed(i64),
+}
+
+fn parse_number(line_index: usize, line: &str, number_str: &str) -> Result<i64, Error> {
+    number_str
+        .parse::<i64>()
+        .map_err(|_| Error::InvalidNumber(Location::new(line_index, line, number_str)))
+}
+
+struct MapLine {
+    destination_start: i64,
+    origin_start: i64,
+    range: i64,
+}
+
+struct SeedRange {
+    start: i64,
+    size: i64,
+}
+
+impl SeedRange {
+    fn end(&self) -> i64 {
+        self.start + self.size
+    }
+    #[allow(dead_code)]
+    fn matches_range(&self, other: &SeedRange) -> bool {
+        if self.start <= other.start {
+            self.end() >= other.start
+        } else {
+            other.end() >= self.start
+        }
+    }
+
+    #[allow(dead_code)]
+    fn get_overlap(&self, other: &SeedRange) -> Option<SeedRange> {
+        if !self.matches_range(other) {
+            trace!("they don't overlap!");
+            return None;
+        }
+
+        if self.start <= other.start {
+            if self.end() >= other.end() {
+                Some(SeedRange {
+                    start: other.start,
+                    size: other.size,
+                })
+            } else {
+                Some(SeedRange {
+                    start: other.start,
+                    size: self.size - other.start,
+                })
+            }
+        } else {
+            if self.end() <= other.end() {
+                return Some(SeedRange {
+                    start: self.start,
+                    size: self.size,
+                });
+            }
+            Some(SeedRange {
+                start: self.start,
+                size: self.start - other.end(),
+            })
+        }
+    }
+}
+
+impl MapLine {
+    fn new(line_index: usize, line: &str) -> Result<MapLine, Error> {
+        let mut numbers_strs = line.split_whitespace();
+        let mut next_number = || match numbers_strs.next() {
+            Some(number_str) => parse_number(line_index, line, number_str),
+            None => Err(Error::IncompleteMapLine(Location::new(
+                line_index, line, line,
+            ))),
+        };
+        let destination_range_start = next_number()?;
+        let source_range_start = next_number()?;
+        let range_length = next_number()?;
+
+        Ok(MapLine {
+            destination_start: destination_range_start,
+            origin_start: source_range_start,
+            range: range_length,
+        })
+    }
+
+    fn is_in_range(&self, number: i64) -> bool {
+        number >= self.origin_start && number < self.origin_start + self.range
+    }
+
+    fn calculate_destination(&self, origin: i64) -> i64 {
+        if self.is_in_range(origin) {
+            return origin - self.origin_start + self.destination_start;
+        }
+
+        origin
+    }
+
+    #[allow(dead_code)]
+    fn is_optimal(&self) -> bool {
+        self.destination_start < self.origin_start
+    }
+
+    #[allow(dead_code)]
+    fn optimal_range(&self) -> SeedRange {
+        if self.is_optimal() {
+            return SeedRange {
+                start: self.origin_start,
+                size: self.range,
+            };
+        }
+
+        SeedRange {
+            start: 0,
+            size: self.origin_start,
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct Map {
+    origin: String,
+    destination: String,
+    lines: Vec<MapLine>,
+}
+
+impl Map {
+    fn new(line_index: usize, line: &str) -> Result<Map, Error> {
+        let (origin, destination) = line
+            .trim()
+            .strip_suffix(" map:")
+            .and_then(|name| name.split_once("-to-"))
+            .ok_or_else(|| Error::InvalidMapHeader(Location::new(line_index, line, line)))?;
+
+        Ok(Map {
+            origin: origin.to_string(),
+            destination: destination.to_string(),
+            lines: vec![],
+        })
+    }
+
+    fn get_destination(&self, origin: i64) -> i64 {
+        for line in &self.lines {
+            if line.is_in_range(origin) {
+                return line.calculate_destination(origin);
+            }
+        }
+
+        origin
+    }
+
+    #[allow(dead_code)]
+    fn get_optimal_path(&self, seed_range: SeedRange) -> SeedRange {
+        let mut optimal_range = seed_range;
+        for i in 0..self.lines.len() {
+            let line_optimal_range = self.lines[i].optimal_range();
+            trace!(
+                "Line optimal range for {} {} {} is: {} {}",
+                self.lines[i].destination_start,
+                self.lines[i].origin_start,
+                self.lines[i].range,
+                line_optimal_range.start,
+                line_optimal_range.size,
+            );
+            optimal_range = line_optimal_range
+                .get_overlap(&optimal_range)
+                .unwrap_or(optimal_range);
+            trace!(
+                "optimal range now is: {} {}",
+                optimal_range.start,
+                optimal_range.size
+            );
+        }
+        optimal_range
+    }
+}
+
+fn get_seeds(seeds_str: &str) -> Result<Vec<i64>, Error> {
+    let numbers_strs = seeds_str
+        .trim()
+        .strip_prefix("seeds:")
+        .ok_or_else(|| Error::MissingSeeds(Location::new(0, seeds_str, seeds_str)))?
+        .split_whitespace();
+    let mut out: Vec<i64> = Vec::new();
+    for number_str in numbers_strs {
+        out.push(parse_number(0, seeds_str, number_str)?)
+    }
+
+    Ok(out)
+}
+
+fn is_starting_map_line(line: &str) -> bool {
+    line.to_string().contains("map:")
+}
+
+fn get_seed_ranges(seeds: &[i64]) -> Result<Vec<SeedRange>, Error> {
+    seeds
+        .chunks(2)
+        .map(|pair| match pair {
+            [start, size] => Ok(SeedRange {
+                start: *start,
+                size: *size,
+            }),
+            _ => Err(Error::UnpairedSeed(pair[0])),
+        })
+        .collect()
+}
+
+pub struct Almanac {
+    seeds: Vec<i64>,
+    maps: Vec<Map>,
+}
+
+impl Almanac {
+    fn get_location(&self, seed: i64) -> i64 {
+        let mut location = seed;
+        for map in &self.maps {
+            location = map.get_destination(location);
+        }
+        location
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Almanac;
+    type Answer = i64;
+    type Error = Error;
+
+    fn parse(&self, input: &str) -> Result<Almanac, Error> {
+        let mut lines = input.lines().enumerate();
+        let seeds = get_seeds(lines.next().map_or("", |(_, line)| line))?;
+        let mut maps: Vec<Map> = vec![];
+        let mut current_map: Option<Map> = None;
+        for (i, line) in lines {
+            if line.is_empty() {
+                if let Some(map) = current_map {
+                    maps.push(map)
+                }
+                current_map = None;
+                continue;
+            }
+
+            if is_starting_map_line(line) {
+                current_map = Some(Map::new(i, line)?);
+                continue;
+            }
+
+            let map_line = MapLine::new(i, line)?;
+            current_map
+                .as_mut()
+                .ok_or_else(|| Error::MissingMapHeader(Location::new(i, line, line)))?
+                .lines
+                .push(map_line);
+        }
+        if let Some(map) = current_map {
+            maps.push(map)
+        }
+
+        Ok(Almanac { seeds, maps })
+    }
+
+    fn part_one(&self, almanac: &Almanac) -> Result<i64, Error> {
+        debug!("seeds: {:?}", almanac.seeds);
+        let mut out = 0;
+        for seed in &almanac.seeds {
+            let location = almanac.get_location(*seed);
+            trace!("seed {seed} ends at location {location}");
+            if out == 0 || location <= out {
+                out = location;
+            }
+        }
+        Ok(out)
+    }
+
+    fn part_two(&self, almanac: &Almanac) -> Result<i64, Error> {
+        let mut out = 0;
+        for seed_range in get_seed_ranges(&almanac.seeds)? {
+            debug!(
+                "checking {} seeds starting at {}",
+                seed_range.size, seed_range.start
+            );
+            for i in seed_range.start..seed_range.end() {
+                let location = almanac.get_location(i);
+                if out == 0 || location <= out {
+                    out = location;
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part_one_example() {
+        assert_eq!(Day5.part_one(&Day5.parse(EXAMPLE).unwrap()), Ok(35));
+    }
+
+    #[test]
+    fn part_two_example() {
+        assert_eq!(Day5.part_two(&Day5.parse(EXAMPLE).unwrap()), Ok(46));
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_text() {
+        let error = Day5
+            .parse("seeds: 79 14\n\nseed-to-soil map:\n50 98\n")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error,
+            Error::IncompleteMapLine(Location {
+                line: 4,
+                column: 1,
+                text: "50 98".to_string(),
+            })
+        );
+
+        let almanac = Day5.parse("seeds: 79 14 55\n").unwrap();
+        assert_eq!(Day5.part_two(&almanac), Err(Error::UnpairedSeed(55)));
+    }
+}
@@ -0,0 +1,172 @@
+use common::{Location, Solution};
+use log::debug;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("expected {expected:?} at {location}")]
+    MissingPrefix {
+        expected: &'static str,
+        location: Location,
+    },
+    #[error("invalid number at {0}")]
+    InvalidNumber(Location),
+    #[error("there are {times} times but {distances} distances")]
+    LengthMismatch { times: usize, distances: usize },
+    #[error("joined number {0} is too large")]
+    TooLarge(String),
+}
+
+struct Race {
+    time: i64,
+    record_distance: i64,
+}
+
+fn calculate_distance(speed: i64, time: i64) -> i64 {
+    speed * time
+}
+
+impl Race {
+    fn new(time: i64, record_distance: i64) -> Race {
+        Race {
+            time,
+            record_distance,
+        }
+    }
+
+    fn spare_time(&self, speed: i64) -> i64 {
+        self.time - speed
+    }
+
+    fn possibilities(&self) -> i64 {
+        let mut possibilities = 0;
+        for i in 0..=self.time {
+            let distance = calculate_distance(i, self.spare_time(i));
+            if distance > self.record_distance {
+                possibilities += 1;
+            }
+        }
+        possibilities
+    }
+}
+
+fn get_input(
+    line_index: usize,
+    line: Option<&str>,
+    prefix: &'static str,
+) -> Result<Vec<String>, Error> {
+    let line = line.unwrap_or("");
+    let clean_line = line
+        .strip_prefix(prefix)
+        .ok_or_else(|| Error::MissingPrefix {
+            expected: prefix.trim_end(),
+            location: Location::new(line_index, line, line),
+        })?;
+
+    let mut out: Vec<String> = vec![];
+    for number_str in clean_line.split_whitespace() {
+        if number_str.parse::<i64>().is_err() {
+            return Err(Error::InvalidNumber(Location::new(
+                line_index, line, number_str,
+            )));
+        }
+        out.push(number_str.to_string());
+    }
+
+    Ok(out)
+}
+
+/// Reads the numbers ignoring the spaces between them, as part two does.
+fn join_numbers(numbers_strs: &[String]) -> Result<i64, Error> {
+    let joined = numbers_strs.concat();
+    joined.parse().map_err(|_| Error::TooLarge(joined))
+}
+
+/// Races sheet as written in the input, numbers are kept as text since part
+/// two reads them joined together.
+pub struct Sheet {
+    times: Vec<String>,
+    distances: Vec<String>,
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = Sheet;
+    type Answer = i64;
+    type Error = Error;
+
+    fn parse(&self, input: &str) -> Result<Sheet, Error> {
+        let mut lines = input.lines();
+        let times = get_input(0, lines.next(), "Time: ")?;
+        let distances = get_input(1, lines.next(), "Distance:")?;
+
+        if times.len() != distances.len() {
+            return Err(Error::LengthMismatch {
+                times: times.len(),
+                distances: distances.len(),
+            });
+        }
+
+        Ok(Sheet { times, distances })
+    }
+
+    fn part_one(&self, sheet: &Sheet) -> Result<i64, Error> {
+        let mut out = 1;
+        for (time, distance) in sheet.times.iter().zip(sheet.distances.iter()) {
+            // Every number is checked to fit while parsing the sheet
+            let race = Race::new(time.parse().unwrap(), distance.parse().unwrap());
+            let possibilities = race.possibilities();
+            debug!("race of {time}ms with record {distance}mm: {possibilities} possibilities");
+            out *= possibilities;
+        }
+
+        Ok(out)
+    }
+
+    fn part_two(&self, sheet: &Sheet) -> Result<i64, Error> {
+        let race = Race::new(join_numbers(&sheet.times)?, join_numbers(&sheet.distances)?);
+        Ok(race.possibilities())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn part_one_example() {
+        assert_eq!(Day6.part_one(&Day6.parse(EXAMPLE).unwrap()), Ok(288));
+    }
+
+    #[test]
+    fn part_two_example() {
+        assert_eq!(Day6.part_two(&Day6.parse(EXAMPLE).unwrap()), Ok(71503));
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_text() {
+        let error = Day6.parse("Time: 7 15\nDistance: 9 4o").err().unwrap();
+        assert_eq!(
+            error,
+            Error::InvalidNumber(Location {
+                line: 2,
+                column: 13,
+                text: "4o".to_string(),
+            })
+        );
+
+        let error = Day6.parse("Time: 7 15\n").err().unwrap();
+        assert!(matches!(
+            error,
+            Error::MissingPrefix {
+                expected: "Distance:",
+                ..
+            }
+        ));
+    }
+}